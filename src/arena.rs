@@ -0,0 +1,260 @@
+use core::cell::RefCell;
+use core::fmt::{self, Debug};
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::Range;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use aliasable::boxed::AliasableBox;
+
+use crate::verify::address_range;
+use crate::{Extents, FromStorage, IntoStorage, Rebind, Target, Zc};
+
+/// Growing set of owners with stable storage.
+///
+/// Each owner added is converted into its storage and never removed until the
+/// arena is dropped, so a single [`Dependant`] can borrow from (and
+/// cross-reference data within) any number of them. Owners can be added while
+/// constructing the dependant via [`ZcArena::add()`], or afterwards via
+/// [`Zc::add()`].
+///
+/// As owners can be added via a shared reference, the arena is not `Sync`.
+///
+/// # Example
+/// ```
+/// use zc::{Zc, ZcArena};
+///
+/// fn symbols(arena: &ZcArena<String>) -> Vec<&str> {
+///     let _ = arena.add(String::from("print"));
+///     arena.iter().flat_map(|source| source.split_whitespace()).collect()
+/// }
+///
+/// let arena: ZcArena<String> = ["let a", "let b"].iter().map(|s| s.to_string()).collect();
+/// let data = Zc::new(arena, symbols);
+///
+/// assert_eq!(data.get::<Vec<&str>>(), &["let", "a", "let", "b", "print"]);
+/// assert_eq!(data.as_owned().len(), 3);
+/// ```
+///
+/// [`Dependant`]: crate::Dependant
+pub struct ZcArena<O = Vec<u8>>
+where
+    O: IntoStorage,
+{
+    storages: RefCell<Vec<O::Storage>>,
+}
+
+impl<O> ZcArena<O>
+where
+    O: IntoStorage,
+{
+    /// Construct a new empty `ZcArena`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            storages: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of owners within the arena.
+    pub fn len(&self) -> usize {
+        self.storages.borrow().len()
+    }
+
+    /// Returns `true` if the arena contains no owners.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds an owner to the arena, returning a reference to its target.
+    pub fn add(&self, owner: O) -> &Target<O> {
+        let storage = IntoStorage::into_storage(owner);
+        let target = erase_target::<O>(&storage);
+        self.storages.borrow_mut().push(storage);
+        // SAFETY: The target of the storage is stable and the storage is not
+        // dropped until the arena is.
+        unsafe { &*target }
+    }
+
+    /// Returns a reference to the target of the owner at the index.
+    pub fn get(&self, index: usize) -> Option<&Target<O>> {
+        let target = self.storages.borrow().get(index).map(erase_target::<O>)?;
+        // SAFETY: See `ZcArena::add()`.
+        Some(unsafe { &*target })
+    }
+
+    /// Returns an iterator over the targets of the owners within the arena.
+    ///
+    /// The iterator does not borrow the list of owners between calls, so
+    /// owners can be added while iterating.
+    pub fn iter(&self) -> Owners<'_, O> {
+        Owners {
+            arena: self,
+            index: 0,
+        }
+    }
+
+    /// Consumes `self` into the owners, in the order they were added.
+    pub fn into_owners(self) -> Vec<O>
+    where
+        O: FromStorage,
+    {
+        self.storages
+            .into_inner()
+            .into_iter()
+            .map(FromStorage::from_storage)
+            .collect()
+    }
+}
+
+fn erase_target<O: IntoStorage>(storage: &O::Storage) -> *const Target<O> {
+    core::ptr::addr_of!(**storage)
+}
+
+impl<O> Default for ZcArena<O>
+where
+    O: IntoStorage,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O> FromIterator<O> for ZcArena<O>
+where
+    O: IntoStorage,
+{
+    fn from_iter<I: IntoIterator<Item = O>>(iter: I) -> Self {
+        let storages = iter.into_iter().map(IntoStorage::into_storage).collect();
+        Self {
+            storages: RefCell::new(storages),
+        }
+    }
+}
+
+impl<O> Debug for ZcArena<O>
+where
+    O: IntoStorage,
+    Target<O>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<O> IntoStorage for ZcArena<O>
+where
+    O: IntoStorage + 'static,
+{
+    type Storage = AliasableBox<Self>;
+
+    fn into_storage(self) -> Self::Storage {
+        AliasableBox::from_unique(Box::new(self))
+    }
+}
+
+impl<O> FromStorage for ZcArena<O>
+where
+    O: IntoStorage + 'static,
+{
+    fn from_storage(storage: Self::Storage) -> Self {
+        *AliasableBox::into_unique(storage)
+    }
+}
+
+impl<O> Extents for ZcArena<O>
+where
+    O: IntoStorage,
+    Target<O>: Extents,
+{
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self));
+        for owned in self {
+            owned.visit_extents(visit);
+        }
+    }
+}
+
+impl<'a, O> IntoIterator for &'a ZcArena<O>
+where
+    O: IntoStorage,
+{
+    type Item = &'a Target<O>;
+    type IntoIter = Owners<'a, O>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the targets of the owners within a [`ZcArena`].
+pub struct Owners<'a, O>
+where
+    O: IntoStorage,
+{
+    arena: &'a ZcArena<O>,
+    index: usize,
+}
+
+impl<'a, O> Iterator for Owners<'a, O>
+where
+    O: IntoStorage,
+{
+    type Item = &'a Target<O>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let target = self.arena.get(self.index)?;
+        self.index += 1;
+        Some(target)
+    }
+}
+
+impl<O> FusedIterator for Owners<'_, O> where O: IntoStorage {}
+
+impl<O> Clone for Owners<'_, O>
+where
+    O: IntoStorage,
+{
+    fn clone(&self) -> Self {
+        Self {
+            arena: self.arena,
+            index: self.index,
+        }
+    }
+}
+
+impl<O> Debug for Owners<'_, O>
+where
+    O: IntoStorage,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Owners")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<O, D> Zc<ZcArena<O>, D>
+where
+    O: IntoStorage + 'static,
+{
+    /// Adds an owner to the [`ZcArena`] and calls a function with its target
+    /// and a mutable reference to the [`Dependant`].
+    ///
+    /// As with [`ZcMut::with_mut()`], the dependant is provided with a fresh
+    /// lifetime, so only data from the arena (including the new owner) can be
+    /// stored within it, and nothing borrowed from the arena can be returned.
+    ///
+    /// [`Dependant`]: crate::Dependant
+    /// [`ZcMut::with_mut()`]: crate::ZcMut::with_mut
+    pub fn add<F, R>(&mut self, owner: O, f: F) -> R
+    where
+        D: for<'a> Rebind<'a>,
+        F: for<'a> FnOnce(&'a Target<O>, &'a mut <D as Rebind<'a>>::Dependant) -> R,
+    {
+        let target = self.storage.add(owner);
+        let value_ptr: *mut D = core::ptr::addr_of_mut!(self.value);
+        f(target, unsafe { &mut *value_ptr.cast() })
+    }
+}
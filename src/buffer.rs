@@ -0,0 +1,232 @@
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use aliasable::boxed::AliasableBox;
+
+use crate::verify::address_range;
+use crate::{Dependant, Extents, FromStorage, IntoStorage, Rebind, Zc};
+
+/// Append-only buffer of bytes with stable storage.
+///
+/// Bytes are pushed as chunks that are never moved or modified once added, so
+/// a [`Dependant`] can borrow from existing chunks while more are appended via
+/// [`Zc::extend()`].
+///
+/// # Example
+/// ```
+/// use zc::{Zc, ZcBuffer};
+///
+/// fn lines(buffer: &ZcBuffer) -> Vec<&[u8]> {
+///     buffer.chunks().flat_map(|chunk| chunk.split(|b| *b == b'\n')).collect()
+/// }
+///
+/// let mut data = Zc::new(ZcBuffer::from(b"a\nb".to_vec()), lines);
+///
+/// data.extend(b"c\nd", |new_bytes, lines| {
+///     lines.extend(new_bytes.split(|b| *b == b'\n'));
+/// });
+///
+/// assert_eq!(data.get::<Vec<&[u8]>>(), &[b"a", b"b", b"c", b"d"]);
+/// assert_eq!(data.as_owned().len(), 6);
+/// ```
+///
+/// [`Dependant`]: crate::Dependant
+pub struct ZcBuffer {
+    // SAFETY: Only modified via `&mut ZcBuffer` or `&mut Zc<ZcBuffer, _>`,
+    // while no other reference to the buffer can be accessed.
+    inner: UnsafeCell<Inner>,
+}
+
+struct Inner {
+    len: usize,
+    chunks: Vec<AliasableBox<[u8]>>,
+}
+
+impl ZcBuffer {
+    /// Construct a new empty `ZcBuffer`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inner: UnsafeCell::new(Inner {
+                len: 0,
+                chunks: Vec::new(),
+            }),
+        }
+    }
+
+    /// Returns the total number of bytes within the buffer.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner().len
+    }
+
+    /// Returns `true` if the buffer contains no bytes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of chunks within the buffer.
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.inner().chunks.len()
+    }
+
+    /// Returns the chunk at the index.
+    #[must_use]
+    pub fn chunk(&self, index: usize) -> Option<&[u8]> {
+        self.inner().chunks.get(index).map(|chunk| &**chunk)
+    }
+
+    /// Returns an iterator over the chunks of the buffer.
+    #[must_use]
+    pub fn chunks(&self) -> Chunks<'_> {
+        Chunks {
+            buffer: self,
+            index: 0,
+        }
+    }
+
+    /// Copies the bytes into a new chunk, returning a reference to it.
+    ///
+    /// Empty bytes are not added as a chunk.
+    pub fn push_bytes(&mut self, bytes: &[u8]) -> &[u8] {
+        // SAFETY: `self` is borrowed mutably.
+        unsafe { self.push_shared(bytes) }
+    }
+
+    /// # Safety
+    /// No other reference to the buffer may be accessed while pushing.
+    unsafe fn push_shared(&self, bytes: &[u8]) -> &[u8] {
+        if bytes.is_empty() {
+            return &[];
+        }
+        let inner = &mut *self.inner.get();
+        inner.len += bytes.len();
+        inner
+            .chunks
+            .push(AliasableBox::from_unique(Box::from(bytes)));
+        let chunk = core::ptr::addr_of!(**inner.chunks.last().unwrap());
+        // The chunk is stable and not tied to the borrow of `inner`.
+        &*chunk
+    }
+
+    fn inner(&self) -> &Inner {
+        // SAFETY: The buffer is not being modified, as that requires the only
+        // access to it.
+        unsafe { &*self.inner.get() }
+    }
+}
+
+impl Default for ZcBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Vec<u8>> for ZcBuffer {
+    fn from(bytes: Vec<u8>) -> Self {
+        let mut buffer = Self::new();
+        if !bytes.is_empty() {
+            let inner = buffer.inner.get_mut();
+            inner.len = bytes.len();
+            inner
+                .chunks
+                .push(AliasableBox::from_unique(bytes.into_boxed_slice()));
+        }
+        buffer
+    }
+}
+
+impl Debug for ZcBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZcBuffer")
+            .field("len", &self.len())
+            .field("chunks", &self.chunk_count())
+            .finish()
+    }
+}
+
+// SAFETY: The buffer is only modified while there is no other access to it.
+unsafe impl Sync for ZcBuffer {}
+
+impl IntoStorage for ZcBuffer {
+    type Storage = AliasableBox<Self>;
+
+    fn into_storage(self) -> Self::Storage {
+        AliasableBox::from_unique(Box::new(self))
+    }
+}
+
+impl FromStorage for ZcBuffer {
+    fn from_storage(storage: Self::Storage) -> Self {
+        *AliasableBox::into_unique(storage)
+    }
+}
+
+impl Extents for ZcBuffer {
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self));
+        self.chunks().map(address_range).for_each(visit);
+    }
+}
+
+/// Iterator over the chunks of a [`ZcBuffer`].
+///
+/// The iterator does not borrow the list of chunks between calls, so it can be
+/// held within a [`Dependant`] while the buffer is extended.
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    buffer: &'a ZcBuffer,
+    index: usize,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.buffer.chunk(self.index)?;
+        self.index += 1;
+        Some(chunk)
+    }
+}
+
+impl FusedIterator for Chunks<'_> {}
+
+// SAFETY: `Chunks` only borrows the buffer, which is never moved by appending
+// to it, and only borrows each chunk while it is yielded.
+unsafe impl<'o> Dependant<'o> for Chunks<'o> {
+    type Static = Chunks<'static>;
+}
+
+unsafe impl<'o> Rebind<'o> for Chunks<'static> {
+    type Dependant = Chunks<'o>;
+}
+
+impl<D> Zc<ZcBuffer, D> {
+    /// Appends the bytes to the [`ZcBuffer`] owner and calls a function with
+    /// the new chunk and a mutable reference to the [`Dependant`].
+    ///
+    /// As with [`ZcMut::with_mut()`], the dependant is provided with a fresh
+    /// lifetime, so only data from the owner (including the new chunk) can be
+    /// stored within it, and nothing borrowed from the owner can be returned.
+    ///
+    /// [`Dependant`]: crate::Dependant
+    /// [`ZcMut::with_mut()`]: crate::ZcMut::with_mut
+    pub fn extend<F, R>(&mut self, bytes: &[u8], f: F) -> R
+    where
+        D: for<'a> Rebind<'a>,
+        F: for<'a> FnOnce(&'a [u8], &'a mut <D as Rebind<'a>>::Dependant) -> R,
+    {
+        // SAFETY: `self` is borrowed mutably, so neither the owner nor the
+        // dependant is accessed elsewhere while pushing.
+        let new_bytes = unsafe { self.storage.push_shared(bytes) };
+        let value_ptr: *mut D = core::ptr::addr_of_mut!(self.value);
+        f(new_bytes, unsafe { &mut *value_ptr.cast() })
+    }
+}
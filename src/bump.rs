@@ -0,0 +1,148 @@
+use core::fmt::{self, Debug};
+use core::ops::Range;
+
+use alloc::boxed::Box;
+
+use aliasable::boxed::AliasableBox;
+use bumpalo::Bump;
+
+use crate::verify::address_range;
+use crate::{Extents, FromStorage, IntoStorage, Target};
+
+/// Owner consisting of a source and a [`Bump`] arena for allocating the
+/// [`Dependant`] constructed from it.
+///
+/// The constructor receives a reference to the `BumpOwner`, from which both
+/// the source and the arena can be borrowed for `'o` via
+/// [`BumpOwner::parts()`]. Dependants can then use `bumpalo` collections
+/// (`Vec<'o, T>`, `String<'o>` and `Box<'o, T>`) and `&'o T` references to
+/// values allocated in the arena, instead of an allocation per node.
+///
+/// As [`Bump`] is not `Sync`, neither is the owner.
+///
+/// # Example
+/// ```
+/// use zc::bumpalo::collections::Vec;
+/// use zc::{BumpOwner, Dependant, Zc};
+///
+/// #[derive(Dependant)]
+/// pub struct Node<'a> {
+///     name: &'a str,
+///     children: Vec<'a, &'a Node<'a>>,
+/// }
+///
+/// fn parse(owner: &BumpOwner<String>) -> Node<'_> {
+///     let (source, bump) = owner.parts();
+///     let mut children = Vec::new_in(bump);
+///     for name in source.split(',') {
+///         let child = Node { name, children: Vec::new_in(bump) };
+///         children.push(&*bump.alloc(child));
+///     }
+///     Node { name: "root", children }
+/// }
+///
+/// let data = Zc::new(BumpOwner::new(String::from("a,b")), parse);
+/// let root = data.get::<Node>();
+///
+/// assert_eq!(root.children.len(), 2);
+/// assert_eq!(root.children[1].name, "b");
+/// ```
+///
+/// [`Dependant`]: crate::Dependant
+pub struct BumpOwner<O>
+where
+    O: IntoStorage,
+{
+    storage: O::Storage,
+    bump: Bump,
+}
+
+impl<O> BumpOwner<O>
+where
+    O: IntoStorage,
+{
+    /// Construct a new `BumpOwner` given the source and a new empty arena.
+    pub fn new(source: O) -> Self {
+        Self::with_bump(source, Bump::new())
+    }
+
+    /// Construct a new `BumpOwner` given the source and the arena.
+    pub fn with_bump(source: O, bump: Bump) -> Self {
+        Self {
+            storage: IntoStorage::into_storage(source),
+            bump,
+        }
+    }
+
+    /// Returns a reference to the source.
+    pub fn source(&self) -> &Target<O> {
+        &self.storage
+    }
+
+    /// Returns a reference to the arena.
+    pub fn bump(&self) -> &Bump {
+        &self.bump
+    }
+
+    /// Returns references to both the source and the arena.
+    pub fn parts(&self) -> (&Target<O>, &Bump) {
+        (self.source(), self.bump())
+    }
+
+    /// Consumes `self` into the source and the arena.
+    pub fn into_parts(self) -> (O, Bump)
+    where
+        O: FromStorage,
+    {
+        (FromStorage::from_storage(self.storage), self.bump)
+    }
+}
+
+impl<O> Debug for BumpOwner<O>
+where
+    O: IntoStorage,
+    Target<O>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpOwner")
+            .field("source", &self.source())
+            .field("allocated", &self.bump.allocated_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<O> IntoStorage for BumpOwner<O>
+where
+    O: IntoStorage + 'static,
+{
+    type Storage = AliasableBox<Self>;
+
+    fn into_storage(self) -> Self::Storage {
+        AliasableBox::from_unique(Box::new(self))
+    }
+}
+
+impl<O> FromStorage for BumpOwner<O>
+where
+    O: IntoStorage + 'static,
+{
+    fn from_storage(storage: Self::Storage) -> Self {
+        *AliasableBox::into_unique(storage)
+    }
+}
+
+impl<O> Extents for BumpOwner<O>
+where
+    O: IntoStorage,
+    Target<O>: Extents,
+{
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self));
+        self.source().visit_extents(visit);
+        // SAFETY: Nothing is allocated within the arena while iterating.
+        for (ptr, len) in unsafe { self.bump.iter_allocated_chunks_raw() } {
+            let start = ptr as usize;
+            visit(start..start + len);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use core::ops::RangeBounds;
+
+use bytes::Bytes;
+
+use crate::{private, IntoStorage, Zc};
+
+impl<O, D> Zc<O, D>
+where
+    O: IntoStorage<Storage = Bytes>,
+{
+    /// Returns a new [`Bytes`] sharing a sub-slice of the owner, without
+    /// copying.
+    ///
+    /// Available for owners stored as [`Bytes`], including [`BytesMut`]
+    /// (which is frozen when the `Zc` is constructed).
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use bytes::Bytes;
+    /// use zc::Zc;
+    ///
+    /// fn header(packet: &[u8]) -> &[u8] {
+    ///     &packet[..2]
+    /// }
+    ///
+    /// let data = Zc::new(Bytes::from_static(b"\x01\x02payload"), header);
+    ///
+    /// assert_eq!(data.slice_owner(2..), Bytes::from_static(b"payload"));
+    /// ```
+    ///
+    /// [`BytesMut`]: bytes::BytesMut
+    #[must_use]
+    pub fn slice_owner<R>(&self, range: R) -> Bytes
+    where
+        R: RangeBounds<usize>,
+    {
+        self.storage.slice(range)
+    }
+
+    /// Returns a new [`Bytes`] sharing the part of the owner a slice borrows
+    /// (such as one held by the [`Dependant`]), without copying.
+    ///
+    /// Returns `None` if the slice does not lie within the owner.
+    ///
+    /// # Example
+    /// ```
+    /// use bytes::BytesMut;
+    /// use zc::{Dependant, Zc};
+    ///
+    /// #[derive(Dependant)]
+    /// pub struct Message<'a> {
+    ///     topic: &'a [u8],
+    ///     payload: &'a [u8],
+    /// }
+    ///
+    /// fn parse(bytes: &[u8]) -> Message<'_> {
+    ///     let (topic, payload) = bytes.split_at(4);
+    ///     Message { topic, payload }
+    /// }
+    ///
+    /// let mut buf = BytesMut::new();
+    /// buf.extend_from_slice(b"newshello");
+    ///
+    /// // `BytesMut` is frozen into `Bytes` without copying.
+    /// let data = Zc::new(buf, parse);
+    /// let payload = data.slice_owner_ref(data.get::<Message>().payload).unwrap();
+    ///
+    /// // The payload can be passed on after `data` is dropped.
+    /// drop(data);
+    /// assert_eq!(payload, &b"hello"[..]);
+    /// ```
+    ///
+    /// [`Dependant`]: crate::Dependant
+    #[must_use]
+    pub fn slice_owner_ref(&self, sub: &[u8]) -> Option<Bytes> {
+        private::span_within(&self.storage, sub).map(|span| self.storage.slice(span))
+    }
+}
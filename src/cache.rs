@@ -0,0 +1,341 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::hash::Hash;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::{Dependant, IntoStorage, Target, Zc};
+
+type EvictFn<K, O, D> = Box<dyn Fn(&K, &Arc<Zc<O, D>>) + Send + Sync>;
+
+/// Least recently used cache of [`Zc`] entries, bounded by either the number
+/// of entries or their total weight (such as the bytes of each owner).
+///
+/// Entries are shared as `Arc<Zc<O, D>>`, so readers of an entry are never
+/// blocked by the cache, and an evicted entry remains valid for as long as it
+/// is held. Entries are loaded on a miss via
+/// [`ZcCache::get_or_insert_with()`], without holding the lock of the cache,
+/// so loads of other keys can proceed concurrently. If the same key is loaded
+/// concurrently, the first entry inserted is kept.
+///
+/// # Example
+/// ```
+/// use zc::{Zc, ZcCache};
+///
+/// fn first_line(source: &str) -> &str {
+///     source.lines().next().unwrap_or_default()
+/// }
+///
+/// let cache = ZcCache::with_max_bytes(16)
+///     .on_evict(|path: &&str, _| println!("evicted {}", path));
+///
+/// let load = |path: &&str| Zc::new(format!("{}\nbody", path), first_line);
+/// let readme = cache.get_or_insert_with("README", load);
+///
+/// assert_eq!(readme.get::<&str>(), &"README");
+/// assert_eq!(cache.weight(), 11);
+///
+/// let _ = cache.get_or_insert_with("LICENSE", load);
+///
+/// // The least recently used entry was evicted to fit the new entry, but
+/// // remains valid for as long as it is held.
+/// assert!(cache.get("README").is_none());
+/// assert_eq!(readme.get::<&str>(), &"README");
+/// ```
+pub struct ZcCache<K, O, D>
+where
+    O: IntoStorage,
+{
+    state: Mutex<State<K, O, D>>,
+    max_weight: usize,
+    weigh: fn(&Zc<O, D>) -> usize,
+    on_evict: Option<EvictFn<K, O, D>>,
+}
+
+struct State<K, O: IntoStorage, D> {
+    tick: u64,
+    weight: usize,
+    entries: HashMap<K, Entry<O, D>>,
+    recency: BTreeMap<u64, K>,
+}
+
+struct Entry<O: IntoStorage, D> {
+    zc: Arc<Zc<O, D>>,
+    weight: usize,
+    tick: u64,
+}
+
+impl<K, O, D> ZcCache<K, O, D>
+where
+    K: Hash + Eq + Clone,
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Construct a new `ZcCache` bounded to a maximum number of entries.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self::with_max_weight(max_entries, |_| 1)
+    }
+
+    /// Construct a new `ZcCache` bounded to a maximum total of bytes held by
+    /// the owners of its entries.
+    ///
+    /// The bytes of each owner are measured when inserted via
+    /// [`Zc::as_owned()`]. Entries larger than `max_bytes` are never inserted
+    /// (see [`ZcCache::with_max_weight()`]).
+    #[must_use]
+    pub fn with_max_bytes(max_bytes: usize) -> Self
+    where
+        Target<O>: AsRef<[u8]>,
+    {
+        Self::with_max_weight(max_bytes, |zc| zc.as_owned().as_ref().len())
+    }
+
+    /// Construct a new `ZcCache` bounded to a maximum total weight of its
+    /// entries, given a function to weigh each entry when inserted.
+    ///
+    /// Entries heavier than `max_weight` are never inserted, as they could
+    /// not fit. They are still returned from
+    /// [`ZcCache::get_or_insert_with()`], but are loaded again on each call,
+    /// and are not passed to the eviction function.
+    #[must_use]
+    pub fn with_max_weight(max_weight: usize, weigh: fn(&Zc<O, D>) -> usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                tick: 0,
+                weight: 0,
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+            }),
+            max_weight,
+            weigh,
+            on_evict: None,
+        }
+    }
+
+    /// Sets a function called with each entry evicted to keep the cache
+    /// within its bound.
+    ///
+    /// The function is not called for entries replaced or removed explicitly,
+    /// and is called without holding the lock of the cache.
+    #[must_use]
+    pub fn on_evict<F>(mut self, on_evict: F) -> Self
+    where
+        F: Fn(&K, &Arc<Zc<O, D>>) + Send + Sync + 'static,
+    {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total weight of the entries in the cache.
+    pub fn weight(&self) -> usize {
+        self.lock().weight
+    }
+
+    /// Returns the maximum total weight of the entries in the cache.
+    pub fn max_weight(&self) -> usize {
+        self.max_weight
+    }
+
+    /// Returns `true` if the cache has an entry for the key, without marking
+    /// it as used.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.lock().entries.contains_key(key)
+    }
+
+    /// Returns the entry for the key, marking it as the most recently used.
+    pub fn get<Q>(&self, key: &Q) -> Option<Arc<Zc<O, D>>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.lock().touch(key)
+    }
+
+    /// Returns the entry for the key, loading and inserting it if missing.
+    pub fn get_or_insert_with<F>(&self, key: K, load: F) -> Arc<Zc<O, D>>
+    where
+        F: FnOnce(&K) -> Zc<O, D>,
+    {
+        match self.try_get_or_insert_with::<_, core::convert::Infallible>(key, |key| Ok(load(key)))
+        {
+            Ok(zc) => zc,
+            Err(err) => match err {},
+        }
+    }
+
+    /// Returns the entry for the key, trying to load and insert it if
+    /// missing.
+    ///
+    /// # Errors
+    /// Returns the error from loading the entry, in which case nothing is
+    /// inserted.
+    pub fn try_get_or_insert_with<F, E>(&self, key: K, load: F) -> Result<Arc<Zc<O, D>>, E>
+    where
+        F: FnOnce(&K) -> Result<Zc<O, D>, E>,
+    {
+        if let Some(zc) = self.get(&key) {
+            return Ok(zc);
+        }
+        let zc = Arc::new(load(&key)?);
+        let mut state = self.lock();
+        if let Some(existing) = state.touch(&key) {
+            return Ok(existing);
+        }
+        let evicted = self.insert_locked(&mut state, key, Arc::clone(&zc));
+        drop(state);
+        self.evict(evicted);
+        Ok(zc)
+    }
+
+    /// Inserts an entry for the key, returning the entry replaced if any.
+    pub fn insert(&self, key: K, zc: Zc<O, D>) -> Option<Arc<Zc<O, D>>> {
+        let mut state = self.lock();
+        let replaced = state.remove(&key);
+        let evicted = self.insert_locked(&mut state, key, Arc::new(zc));
+        drop(state);
+        self.evict(evicted);
+        replaced
+    }
+
+    /// Removes the entry for the key, returning it if present.
+    pub fn remove<Q>(&self, key: &Q) -> Option<Arc<Zc<O, D>>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        self.lock().remove(key)
+    }
+
+    /// Removes all entries from the cache.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.weight = 0;
+        state.recency.clear();
+        let entries = core::mem::take(&mut state.entries);
+        drop(state);
+        drop(entries);
+    }
+
+    fn insert_locked(
+        &self,
+        state: &mut State<K, O, D>,
+        key: K,
+        zc: Arc<Zc<O, D>>,
+    ) -> Vec<(K, Arc<Zc<O, D>>)> {
+        let weight = (self.weigh)(&zc);
+        // An entry that could never fit is not inserted, rather than evicting
+        // every entry and then itself.
+        if weight > self.max_weight {
+            return Vec::new();
+        }
+        // The key is cloned and hashed before anything else is updated, so the
+        // state is left consistent if either panics.
+        let recency_key = key.clone();
+        let tick = state.next_tick();
+        let _ = state.entries.insert(key, Entry { zc, weight, tick });
+        let _ = state.recency.insert(tick, recency_key);
+        state.weight += weight;
+        let mut evicted = Vec::new();
+        while state.weight > self.max_weight {
+            match state.pop_least_recent() {
+                Some(entry) => evicted.push(entry),
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    // Called without holding the lock, so evicted entries are also dropped
+    // without holding it.
+    fn evict(&self, evicted: Vec<(K, Arc<Zc<O, D>>)>) {
+        for (key, zc) in evicted {
+            if let Some(on_evict) = &self.on_evict {
+                on_evict(&key, &zc);
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<K, O, D>> {
+        // The state is left consistent if a panic occurs while locked.
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<K, O, D> State<K, O, D>
+where
+    K: Hash + Eq,
+    O: IntoStorage,
+{
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn touch<Q>(&mut self, key: &Q) -> Option<Arc<Zc<O, D>>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        let key = self.recency.remove(&entry.tick)?;
+        entry.tick = tick;
+        let _ = self.recency.insert(tick, key);
+        Some(Arc::clone(&entry.zc))
+    }
+
+    fn remove<Q>(&mut self, key: &Q) -> Option<Arc<Zc<O, D>>>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Hash + Eq,
+    {
+        let entry = self.entries.remove(key)?;
+        let _ = self.recency.remove(&entry.tick);
+        self.weight -= entry.weight;
+        Some(entry.zc)
+    }
+
+    fn pop_least_recent(&mut self) -> Option<(K, Arc<Zc<O, D>>)> {
+        let (&tick, key) = self.recency.iter().next()?;
+        let entry = self.entries.remove(key)?;
+        let key = self.recency.remove(&tick)?;
+        self.weight -= entry.weight;
+        Some((key, entry.zc))
+    }
+}
+
+impl<K, O, D> Debug for ZcCache<K, O, D>
+where
+    K: Hash + Eq + Clone,
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ZcCache")
+            .field("len", &state.entries.len())
+            .field("weight", &state.weight)
+            .field("max_weight", &self.max_weight)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,261 @@
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display};
+use std::io::{self, ErrorKind};
+
+use bytes::{Buf, Bytes, BytesMut};
+use tokio_util::codec::Decoder;
+
+use crate::io::{Framing, LengthPrefix};
+use crate::private::TryConstruct;
+use crate::{Dependant, Zc};
+
+const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+/// [`Decoder`] splitting frames out of a stream of bytes, and constructing a
+/// [`Zc`] with a [`Bytes`] owner per frame.
+///
+/// Each frame is split out of the read buffer and frozen without copying, so
+/// the dependant of each `Zc` borrows directly from the bytes read. Wrapped in
+/// a [`FramedRead`], the result is a stream of `Zc<Bytes, D>` frames.
+///
+/// Frames longer than the maximum frame length (8 MiB by default) fail with
+/// [`ErrorKind::InvalidData`], so a corrupt length or missing delimiter cannot
+/// grow the read buffer without bound.
+///
+/// # Example
+/// ```
+/// use tokio::io::AsyncWriteExt;
+/// use tokio_stream::StreamExt;
+/// use tokio_util::codec::FramedRead;
+/// use zc::io::LengthPrefix;
+/// use zc::{Dependant, ZcCodec};
+///
+/// #[derive(Dependant)]
+/// pub struct Message<'a> {
+///     topic: &'a [u8],
+///     payload: &'a [u8],
+/// }
+///
+/// fn parse(frame: &[u8]) -> Result<Message<'_>, &'static str> {
+///     let (&len, rest) = frame.split_first().ok_or("empty frame")?;
+///     let len = usize::from(len);
+///     if rest.len() < len {
+///         return Err("truncated topic");
+///     }
+///     let (topic, payload) = rest.split_at(len);
+///     Ok(Message { topic, payload })
+/// }
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let (mut client, server) = tokio::io::duplex(64);
+/// client.write_all(b"\x00\x06\x02hiabc").await.unwrap();
+/// drop(client);
+///
+/// let codec = ZcCodec::length_prefixed(LengthPrefix::U16Be, parse);
+/// let mut frames = FramedRead::new(server, codec);
+/// let frame = frames.next().await.unwrap().unwrap();
+///
+/// assert_eq!(frame.get::<Message>().topic, b"hi");
+/// assert_eq!(frame.get::<Message>().payload, b"abc");
+/// assert!(frames.next().await.is_none());
+/// # });
+/// ```
+///
+/// [`FramedRead`]: tokio_util::codec::FramedRead
+#[derive(Clone)]
+pub struct ZcCodec<C> {
+    constructor: C,
+    framing: Framing,
+    max_frame_len: usize,
+    // Length of the read buffer already searched for a delimiter.
+    searched: usize,
+}
+
+impl<C> ZcCodec<C> {
+    /// Construct a new `ZcCodec` splitting frames on a delimiter, which is not
+    /// included in the frames.
+    ///
+    /// A final frame without a trailing delimiter is still decoded at the end
+    /// of the stream.
+    pub fn delimited(delimiter: u8, constructor: C) -> Self {
+        Self::new(Framing::Delimiter(delimiter), constructor)
+    }
+
+    /// Construct a new `ZcCodec` reading frames each prefixed with their
+    /// length.
+    pub fn length_prefixed(prefix: LengthPrefix, constructor: C) -> Self {
+        Self::new(Framing::LengthPrefix(prefix), constructor)
+    }
+
+    fn new(framing: Framing, constructor: C) -> Self {
+        Self {
+            constructor,
+            framing,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            searched: 0,
+        }
+    }
+
+    /// Sets the maximum length of a frame, excluding its delimiter or length
+    /// prefix.
+    #[must_use]
+    pub fn max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    fn split_frame(&mut self, src: &mut BytesMut, eof: bool) -> io::Result<Option<Bytes>> {
+        match self.framing {
+            Framing::Delimiter(delimiter) => self.split_delimited(delimiter, src, eof),
+            Framing::LengthPrefix(prefix) => self.split_length_prefixed(prefix, src, eof),
+        }
+    }
+
+    fn split_delimited(
+        &mut self,
+        delimiter: u8,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> io::Result<Option<Bytes>> {
+        let start = self.searched.min(src.len());
+        let len = match src[start..].iter().position(|b| *b == delimiter) {
+            Some(offset) => start + offset,
+            None if eof && !src.is_empty() => src.len(),
+            None => {
+                self.searched = src.len();
+                return self.check_len(src.len()).map(|()| None);
+            }
+        };
+        self.searched = 0;
+        self.check_len(len)?;
+        let mut frame = src.split_to((len + 1).min(src.len())).freeze();
+        frame.truncate(len);
+        Ok(Some(frame))
+    }
+
+    fn split_length_prefixed(
+        &self,
+        prefix: LengthPrefix,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> io::Result<Option<Bytes>> {
+        let width = prefix.width();
+        if src.len() >= width {
+            let len = usize::try_from(prefix.decode(&src[..width])).unwrap_or(usize::MAX);
+            self.check_len(len)?;
+            if src.len() - width >= len {
+                src.advance(width);
+                return Ok(Some(src.split_to(len).freeze()));
+            }
+            src.reserve(len - (src.len() - width));
+        }
+        if eof && !src.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "stream ended within a frame",
+            ));
+        }
+        Ok(None)
+    }
+
+    fn check_len(&self, len: usize) -> io::Result<()> {
+        if len > self.max_frame_len {
+            Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "frame exceeded the maximum length",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<C, D, E> ZcCodec<C>
+where
+    D: Dependant<'static>,
+    E: 'static,
+    C: for<'o> TryConstruct<'o, [u8], Error = E, Dependant = D> + Clone,
+{
+    fn construct(&self, frame: Option<Bytes>) -> Result<Option<Zc<Bytes, D>>, CodecError<E>> {
+        match frame {
+            Some(frame) => Zc::try_new(frame, self.constructor.clone())
+                .map(Some)
+                .map_err(|(err, frame)| CodecError::Construct(err, frame)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<C, D, E> Decoder for ZcCodec<C>
+where
+    D: Dependant<'static>,
+    E: 'static,
+    C: for<'o> TryConstruct<'o, [u8], Error = E, Dependant = D> + Clone,
+{
+    type Item = Zc<Bytes, D>;
+    type Error = CodecError<E>;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.split_frame(src, false)?;
+        self.construct(frame)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame = self.split_frame(src, true)?;
+        self.construct(frame)
+    }
+}
+
+impl<C> Debug for ZcCodec<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZcCodec")
+            .field("framing", &self.framing)
+            .field("max_frame_len", &self.max_frame_len)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error returned from decoding with a [`ZcCodec`].
+#[derive(Debug)]
+pub enum CodecError<E> {
+    /// The stream failed to be read, or a frame was invalid.
+    Io(io::Error),
+    /// The [`Dependant`] failed to be constructed from the frame, returned
+    /// along with its bytes.
+    ///
+    /// [`Dependant`]: crate::Dependant
+    Construct(E, Bytes),
+}
+
+impl<E> From<io::Error> for CodecError<E> {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl<E: Display> Display for CodecError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to decode frame: {err}"),
+            Self::Construct(err, frame) => {
+                write!(
+                    f,
+                    "failed to construct dependant from {} byte frame: {err}",
+                    frame.len()
+                )
+            }
+        }
+    }
+}
+
+impl<E> std::error::Error for CodecError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Construct(err, _) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use core::fmt::{self, Debug, Write};
+use core::ops::Range;
+use core::str;
+
+use alloc::vec::Vec;
+
+use crate::verify::address_range;
+use crate::{Dependant, IntoStorage, References, Target, Zc};
+
+const HEXDUMP_WIDTH: usize = 16;
+
+/// Configurable [`Debug`] formatter for a [`Zc`], returned from
+/// [`Zc::debug_view()`].
+///
+/// Unlike the [`Debug`] implementation of [`Zc`], the storage is not required
+/// to implement [`Debug`] and is omitted by default. When the target of the
+/// storage is bytes, it can instead be rendered as a hexdump or a text excerpt,
+/// optionally truncated, with the bytes borrowed by the [`Dependant`] marked.
+///
+/// The references held by the [`Dependant`] can be listed (and the bytes they
+/// borrow marked) via [`DebugView::references()`] if it implements
+/// [`References`]. They are listed in the order visited (the order of the
+/// fields) as offsets into the owner, with any references outside of the owner
+/// listed as addresses.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, References, Zc};
+///
+/// #[derive(Debug, Dependant, References)]
+/// pub struct Request<'a> {
+///     method: &'a str,
+///     path: &'a str,
+/// }
+///
+/// fn parse(source: &str) -> Request<'_> {
+///     let mut parts = source.split(' ');
+///     Request {
+///         method: parts.next().unwrap(),
+///         path: parts.next().unwrap(),
+///     }
+/// }
+///
+/// let data = Zc::new(String::from("GET /index.html HTTP/1.1"), parse);
+///
+/// assert_eq!(
+///     format!("{:?}", data.debug_view().references()),
+///     r#"Zc { value: Request { method: "GET", path: "/index.html" }, references: [0..3, 4..15], .. }"#
+/// );
+/// assert_eq!(
+///     format!("{:#?}", data.debug_view().references().text()),
+///     r#"Zc {
+///     value: Request {
+///         method: "GET",
+///         path: "/index.html",
+///     },
+///     references: [
+///         0..3,
+///         4..15,
+///     ],
+///     owner: 24 bytes
+///        1 | GET /index.html HTTP/1.1
+///          | ^^^ ^^^^^^^^^^^,
+/// }"#
+/// );
+/// ```
+pub struct DebugView<'z, O, D>
+where
+    O: IntoStorage,
+{
+    zc: &'z Zc<O, D>,
+    references: Option<ReferencesFn<D>>,
+    owner: Option<(Format, BytesFn<O>)>,
+    limit: Option<usize>,
+}
+
+type ReferencesFn<D> = fn(&D, &mut dyn FnMut(Range<usize>));
+
+type BytesFn<O> = for<'a> fn(&'a Target<O>) -> &'a [u8];
+
+#[derive(Clone, Copy)]
+enum Format {
+    Hexdump,
+    Text,
+}
+
+impl<O, D> Zc<O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Returns a [`DebugView`] for formatting `self` without requiring the
+    /// storage to implement [`Debug`].
+    pub fn debug_view(&self) -> DebugView<'_, O, D> {
+        DebugView {
+            zc: self,
+            references: None,
+            owner: None,
+            limit: None,
+        }
+    }
+}
+
+impl<O, D> DebugView<'_, O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Lists the references held by the [`Dependant`], marking the bytes they
+    /// borrow when the owner is rendered.
+    #[must_use]
+    pub fn references(mut self) -> Self
+    where
+        D: References,
+    {
+        self.references = Some(<D as References>::visit_references);
+        self
+    }
+
+    /// Omits the owner from the output (the default).
+    #[must_use]
+    pub fn omit_owner(mut self) -> Self {
+        self.owner = None;
+        self
+    }
+
+    /// Renders the owner as a hexdump, marking the bytes borrowed.
+    #[must_use]
+    pub fn hexdump(mut self) -> Self
+    where
+        Target<O>: AsRef<[u8]>,
+    {
+        self.owner = Some((Format::Hexdump, <Target<O> as AsRef<[u8]>>::as_ref));
+        self
+    }
+
+    /// Renders the owner as lines of text, marking the characters borrowed.
+    ///
+    /// Bytes that are not valid UTF-8 and control characters are rendered as
+    /// `.`.
+    #[must_use]
+    pub fn text(mut self) -> Self
+    where
+        Target<O>: AsRef<[u8]>,
+    {
+        self.owner = Some((Format::Text, <Target<O> as AsRef<[u8]>>::as_ref));
+        self
+    }
+
+    /// Truncates the owner rendered to the first `len` bytes.
+    #[must_use]
+    pub fn truncate(mut self, len: usize) -> Self {
+        self.limit = Some(len);
+        self
+    }
+
+    fn spans(&self, owner: Range<usize>) -> Vec<Span> {
+        let mut spans = Vec::new();
+        if let Some(visit_references) = self.references {
+            visit_references(&self.zc.value, &mut |reference| {
+                spans.push(
+                    if owner.start <= reference.start && reference.end <= owner.end {
+                        Span::Offset(reference.start - owner.start..reference.end - owner.start)
+                    } else {
+                        Span::Address(reference)
+                    },
+                );
+            });
+        }
+        spans
+    }
+}
+
+impl<O, D> Debug for DebugView<'_, O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static> + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let target = &*self.zc.storage;
+        let bytes = self
+            .owner
+            .map(|(format, as_bytes)| (format, as_bytes(target)));
+        let spans = self.spans(bytes.map_or_else(|| address_range(target), |b| address_range(b.1)));
+        let mut debug = f.debug_struct("Zc");
+        let _ = debug.field("value", &self.zc.value);
+        if self.references.is_some() {
+            let _ = debug.field("references", &spans);
+        }
+        match bytes {
+            Some((format, bytes)) => {
+                let owner = OwnerView {
+                    format,
+                    bytes,
+                    spans: &spans,
+                    limit: self.limit.unwrap_or(bytes.len()).min(bytes.len()),
+                };
+                debug.field("owner", &owner).finish()
+            }
+            None => debug.finish_non_exhaustive(),
+        }
+    }
+}
+
+enum Span {
+    Offset(Range<usize>),
+    Address(Range<usize>),
+}
+
+impl Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Offset(range) => write!(f, "{}..{}", range.start, range.end),
+            Self::Address(range) => write!(f, "{:#x}..{:#x}", range.start, range.end),
+        }
+    }
+}
+
+struct OwnerView<'a> {
+    format: Format,
+    bytes: &'a [u8],
+    spans: &'a [Span],
+    limit: usize,
+}
+
+impl OwnerView<'_> {
+    fn marked(&self) -> Vec<bool> {
+        let mut marked = alloc::vec![false; self.limit];
+        for span in self.spans {
+            if let Span::Offset(range) = span {
+                let end = range.end.min(self.limit);
+                if range.start < end {
+                    marked[range.start..end].fill(true);
+                }
+            }
+        }
+        marked
+    }
+
+    fn fmt_hexdump(&self, f: &mut fmt::Formatter<'_>, marked: &[bool]) -> fmt::Result {
+        let bytes = &self.bytes[..self.limit];
+        for (index, (row, row_marked)) in bytes
+            .chunks(HEXDUMP_WIDTH)
+            .zip(marked.chunks(HEXDUMP_WIDTH))
+            .enumerate()
+        {
+            write!(f, "\n{:08x} ", index * HEXDUMP_WIDTH)?;
+            for (column, byte) in row.iter().enumerate() {
+                write!(f, "{}{byte:02x}", column_sep(column))?;
+            }
+            for column in row.len()..HEXDUMP_WIDTH {
+                write!(f, "{}  ", column_sep(column))?;
+            }
+            f.write_str("  |")?;
+            for byte in row {
+                f.write_char(printable_byte(*byte))?;
+            }
+            f.write_char('|')?;
+            if row_marked.contains(&true) {
+                let end = row_marked.iter().rposition(|m| *m).map_or(0, |i| i + 1);
+                f.write_str("\n         ")?;
+                for (column, is_marked) in row_marked[..end].iter().enumerate() {
+                    let caret = if *is_marked { "^^" } else { "  " };
+                    write!(f, "{}{caret}", column_sep(column))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_text(&self, f: &mut fmt::Formatter<'_>, marked: &[bool]) -> fmt::Result {
+        let mut start = 0;
+        let mut markers = Vec::new();
+        for (index, line) in self.bytes[..self.limit].split(|b| *b == b'\n').enumerate() {
+            markers.clear();
+            write!(f, "\n{:>4} | ", index + 1)?;
+            let line_marked = &marked[start..start + line.len()];
+            if let Ok(line) = str::from_utf8(line) {
+                for (offset, c) in line.char_indices() {
+                    f.write_char(printable(c))?;
+                    let len = c.len_utf8();
+                    markers.push(line_marked[offset..offset + len].contains(&true));
+                }
+            } else {
+                for byte in line {
+                    f.write_char(printable_byte(*byte))?;
+                }
+                markers.extend_from_slice(line_marked);
+            }
+            if let Some(end) = markers.iter().rposition(|m| *m) {
+                f.write_str("\n     | ")?;
+                for marked in &markers[..=end] {
+                    f.write_char(if *marked { '^' } else { ' ' })?;
+                }
+            }
+            start += line.len() + 1;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for OwnerView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marked = self.marked();
+        write!(f, "{} bytes", self.bytes.len())?;
+        match self.format {
+            Format::Hexdump => self.fmt_hexdump(f, &marked)?,
+            Format::Text => self.fmt_text(f, &marked)?,
+        }
+        if self.limit < self.bytes.len() {
+            write!(f, "\n... {} more bytes", self.bytes.len() - self.limit)?;
+        }
+        Ok(())
+    }
+}
+
+fn column_sep(column: usize) -> &'static str {
+    if column == HEXDUMP_WIDTH / 2 {
+        "  "
+    } else {
+        " "
+    }
+}
+
+fn printable(c: char) -> char {
+    if c.is_control() {
+        '.'
+    } else {
+        c
+    }
+}
+
+fn printable_byte(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        char::from(byte)
+    } else {
+        '.'
+    }
+}
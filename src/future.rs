@@ -0,0 +1,117 @@
+use core::ops::Deref;
+
+use crate::private::{erase_lifetime, ConstructAsync};
+use crate::{Dependant, IntoStorage, Zc};
+
+impl<O, D> Zc<O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Construct a new zero-copied structure given an owner and an async
+    /// function for constructing the [`Dependant`].
+    ///
+    /// The future returned by the function borrows the owned data across its
+    /// await points, and is driven to completion before the owner is moved
+    /// into the `Zc`. If the returned future is dropped before completing, the
+    /// owner is dropped along with it.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::{Dependant, Zc};
+    ///
+    /// #[derive(Dependant)]
+    /// pub struct Request<'a> {
+    ///     method: &'a str,
+    ///     path: &'a str,
+    /// }
+    ///
+    /// async fn parse(source: &str) -> Request<'_> {
+    ///     let (method, rest) = source.split_once(' ').unwrap();
+    ///     // Eg. look up the route asynchronously while holding the method.
+    ///     let path = async { rest.split(' ').next().unwrap() }.await;
+    ///     Request { method, path }
+    /// }
+    ///
+    /// # let block_on = |future| tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future);
+    /// let data = block_on(Zc::new_async(String::from("GET /index.html HTTP/1.1"), parse));
+    ///
+    /// assert_eq!(data.get::<Request>().path, "/index.html");
+    /// ```
+    pub async fn new_async<C>(owner: O, constructor: C) -> Self
+    where
+        C: for<'o> ConstructAsync<'o, <O::Storage as Deref>::Target, Dependant = D>,
+    {
+        let storage = IntoStorage::into_storage(owner);
+        // Create a temporary dependant given the target reference, ending the
+        // borrow of the storage once complete.
+        let value = constructor.construct_async(&*storage).await;
+        // SAFETY: The dependant is stored alongside the storage it references.
+        let value = unsafe { erase_lifetime(value) };
+        Self::from_raw_parts(value, storage)
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio {
+    use core::convert::TryFrom;
+    use std::io::{self, ErrorKind};
+
+    use alloc::vec::Vec;
+
+    use ::tokio::io::{AsyncRead, AsyncReadExt};
+
+    use crate::private::Construct;
+    use crate::{Dependant, Zc};
+
+    impl<D> Zc<Vec<u8>, D>
+    where
+        D: Dependant<'static>,
+    {
+        /// Construct a new zero-copied structure given an async reader, read
+        /// to the end into a `Vec<u8>` owner of at most `max_len` bytes, and a
+        /// function for constructing the [`Dependant`].
+        ///
+        /// # Example
+        /// ```
+        /// use zc::Zc;
+        ///
+        /// fn first_line(body: &[u8]) -> &[u8] {
+        ///     body.split(|b| *b == b'\n').next().unwrap()
+        /// }
+        ///
+        /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+        /// let body = &b"name=zc\nversion=0.4"[..];
+        /// let data = Zc::from_async_read(body, 1024, first_line).await.unwrap();
+        ///
+        /// assert_eq!(data.get::<&[u8]>(), b"name=zc");
+        /// assert!(Zc::from_async_read(body, 8, first_line).await.is_err());
+        /// # });
+        /// ```
+        ///
+        /// # Errors
+        /// Returns an error if reading failed, or with [`ErrorKind::InvalidData`]
+        /// if the reader has more than `max_len` bytes.
+        pub async fn from_async_read<R, C>(
+            reader: R,
+            max_len: usize,
+            constructor: C,
+        ) -> io::Result<Self>
+        where
+            R: AsyncRead + Unpin,
+            C: for<'o> Construct<'o, [u8], Dependant = D>,
+        {
+            let mut owner = Vec::new();
+            // Read one byte past the maximum to detect a reader exceeding it.
+            let limit = u64::try_from(max_len).map_or(u64::MAX, |len| len.saturating_add(1));
+            let _ = reader.take(limit).read_to_end(&mut owner).await?;
+            if owner.len() > max_len {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "reader exceeded the maximum size",
+                ));
+            }
+            Ok(Self::new(owner, constructor))
+        }
+    }
+}
@@ -3,7 +3,7 @@ use core::num::{
     NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
 };
 
-use crate::Dependant;
+use crate::{Dependant, Rebind};
 
 ///////////////////////////////////////////////////////////////////////////////
 // Dependant impl
@@ -14,6 +14,18 @@ macro_rules! impl_dependant_ref {
             unsafe impl<'o> Dependant<'o> for &'o $ty {
                 type Static = &'static $ty;
             }
+
+            unsafe impl<'o> Rebind<'o> for &'static $ty {
+                type Dependant = &'o $ty;
+            }
+
+            unsafe impl<'o> Dependant<'o> for &'o mut $ty {
+                type Static = &'static mut $ty;
+            }
+
+            unsafe impl<'o> Rebind<'o> for &'static mut $ty {
+                type Dependant = &'o mut $ty;
+            }
         )*
     };
 }
@@ -24,6 +36,21 @@ macro_rules! impl_dependant {
             unsafe impl<'o> Dependant<'o> for $ty {
                 type Static = $ty;
             }
+
+            unsafe impl<'o> Rebind<'o> for $ty {
+                type Dependant = $ty;
+            }
+        )*
+    };
+}
+
+// Implements `Rebind` for a generic `Dependant` over `T`.
+macro_rules! impl_rebind_generic {
+    ($($wrapper:ident),*) => {
+        $(
+            unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for $wrapper<T> {
+                type Dependant = $wrapper<T::Dependant>;
+            }
         )*
     };
 }
@@ -56,6 +83,18 @@ unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for &'o T {
     type Static = &'static T::Static;
 }
 
+unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for &'static T {
+    type Dependant = &'o T::Dependant;
+}
+
+unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for &'o mut T {
+    type Static = &'static mut T::Static;
+}
+
+unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for &'static mut T {
+    type Dependant = &'o mut T::Dependant;
+}
+
 unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for Option<T> {
     type Static = Option<T::Static>;
 }
@@ -72,6 +111,16 @@ where
     type Static = Result<T::Static, E::Static>;
 }
 
+unsafe impl<'o, T, E> Rebind<'o> for Result<T, E>
+where
+    T: Rebind<'o>,
+    E: Rebind<'o>,
+{
+    type Dependant = Result<T::Dependant, E::Dependant>;
+}
+
+impl_rebind_generic!(Option, Wrapping);
+
 ///////////////////////////////////////////////////////////////////////////////
 // alloc
 
@@ -79,13 +128,15 @@ where
 mod alloc {
     use alloc::{
         collections::{BTreeMap, BTreeSet, BinaryHeap},
+        rc::Rc,
         string::String,
+        sync::Arc,
         vec::Vec,
     };
 
     use aliasable::{boxed::AliasableBox, string::AliasableString, vec::AliasableVec};
 
-    use crate::{Dependant, Owner, Storage};
+    use crate::{Dependant, FromStorage, IntoStorage, Rebind, Storage, StorageMut};
 
     ///////////////////////////////////////////////////////////////////////////
     // Storage impl
@@ -93,29 +144,42 @@ mod alloc {
     unsafe impl Storage for AliasableString {}
     unsafe impl<T: 'static> Storage for AliasableVec<T> {}
     unsafe impl<T: ?Sized + 'static> Storage for AliasableBox<T> {}
+    unsafe impl<T: ?Sized + 'static> Storage for Rc<T> {}
+    unsafe impl<T: ?Sized + 'static> Storage for Arc<T> {}
+
+    ///////////////////////////////////////////////////////////////////////////
+    // StorageMut impl
+
+    unsafe impl StorageMut for AliasableString {}
+    unsafe impl<T: 'static> StorageMut for AliasableVec<T> {}
+    unsafe impl<T: ?Sized + 'static> StorageMut for AliasableBox<T> {}
 
     ///////////////////////////////////////////////////////////////////////////
-    // Owner impl
+    // IntoStorage / FromStorage impl
 
-    impl Owner for String {
+    impl IntoStorage for String {
         type Storage = AliasableString;
 
         fn into_storage(self) -> Self::Storage {
             Self::Storage::from_unique(self)
         }
+    }
 
+    impl FromStorage for String {
         fn from_storage(storage: Self::Storage) -> Self {
             Self::Storage::into_unique(storage)
         }
     }
 
-    impl<T: 'static> Owner for Vec<T> {
+    impl<T: 'static> IntoStorage for Vec<T> {
         type Storage = AliasableVec<T>;
 
         fn into_storage(self) -> Self::Storage {
             Self::Storage::from_unique(self)
         }
+    }
 
+    impl<T: 'static> FromStorage for Vec<T> {
         fn from_storage(storage: Self::Storage) -> Self {
             Self::Storage::into_unique(storage)
         }
@@ -145,6 +209,16 @@ mod alloc {
     {
         type Static = BTreeMap<K::Static, V::Static>;
     }
+
+    unsafe impl<'o, K, V> Rebind<'o> for BTreeMap<K, V>
+    where
+        K: Rebind<'o>,
+        V: Rebind<'o>,
+    {
+        type Dependant = BTreeMap<K::Dependant, V::Dependant>;
+    }
+
+    impl_rebind_generic!(Vec, BTreeSet, BinaryHeap);
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -155,7 +229,7 @@ mod std {
     use std::collections::{HashMap, HashSet};
     use std::hash::BuildHasher;
 
-    use crate::Dependant;
+    use crate::{Dependant, Rebind};
 
     ///////////////////////////////////////////////////////////////////////////
     // Dependant impl
@@ -176,6 +250,107 @@ mod std {
     {
         type Static = HashMap<K::Static, V::Static, S>;
     }
+
+    unsafe impl<'o, T, S> Rebind<'o> for HashSet<T, S>
+    where
+        T: Rebind<'o>,
+        S: BuildHasher + 'static,
+    {
+        type Dependant = HashSet<T::Dependant, S>;
+    }
+
+    unsafe impl<'o, K, V, S> Rebind<'o> for HashMap<K, V, S>
+    where
+        K: Rebind<'o>,
+        V: Rebind<'o>,
+        S: BuildHasher + 'static,
+    {
+        type Dependant = HashMap<K::Dependant, V::Dependant, S>;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// bumpalo
+
+#[cfg(feature = "bump")]
+mod bump {
+    use bumpalo::{boxed::Box, collections::String, collections::Vec};
+
+    use crate::{Dependant, Rebind};
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Dependant impl
+
+    unsafe impl<'o> Dependant<'o> for String<'o> {
+        type Static = String<'static>;
+    }
+
+    unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for Vec<'o, T> {
+        type Static = Vec<'static, T::Static>;
+    }
+
+    unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for Box<'o, T> {
+        type Static = Box<'static, T::Static>;
+    }
+
+    unsafe impl<'o> Rebind<'o> for String<'static> {
+        type Dependant = String<'o>;
+    }
+
+    unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for Vec<'static, T> {
+        type Dependant = Vec<'o, T::Dependant>;
+    }
+
+    unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for Box<'static, T> {
+        type Dependant = Box<'o, T::Dependant>;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// bytes
+
+#[cfg(feature = "bytes")]
+mod bytes {
+    use bytes::{Bytes, BytesMut};
+
+    use crate::{IntoStorage, Storage};
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Storage impl
+
+    // The data behind `Bytes` is never moved or modified while referenced,
+    // and is shared rather than copied when cloned.
+    unsafe impl Storage for Bytes {}
+
+    ///////////////////////////////////////////////////////////////////////////
+    // IntoStorage impl
+
+    // `BytesMut` is frozen without copying, but cannot be recovered without
+    // copying if the bytes have been shared, so it is only an `IntoStorage`.
+    impl IntoStorage for BytesMut {
+        type Storage = Bytes;
+
+        fn into_storage(self) -> Self::Storage {
+            self.freeze()
+        }
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// memmap2
+
+#[cfg(feature = "mmap")]
+mod mmap {
+    use memmap2::Mmap;
+
+    use crate::Storage;
+
+    ///////////////////////////////////////////////////////////////////////////
+    // Storage impl
+
+    // The mapped memory is not moved when the `Mmap` is moved. The safety of
+    // the mapped data itself is upheld by the `unsafe` constructors of `Mmap`.
+    unsafe impl Storage for Mmap {}
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -186,6 +361,10 @@ macro_rules! impl_dependant_tuple {
         unsafe impl<'o, $($name: Dependant<'o>),+ > Dependant<'o> for ($($name,)+) {
             type Static = ($($name::Static,)+);
         }
+
+        unsafe impl<'o, $($name: Rebind<'o>),+ > Rebind<'o> for ($($name,)+) {
+            type Dependant = ($($name::Dependant,)+);
+        }
     }
 }
 
@@ -194,6 +373,10 @@ macro_rules! impl_dependant_array {
     ($($n:literal)+) => {
         $(unsafe impl<'o, T: Dependant<'o>> Dependant<'o> for [T; $n] {
             type Static = [T::Static; $n];
+        }
+
+        unsafe impl<'o, T: Rebind<'o>> Rebind<'o> for [T; $n] {
+            type Dependant = [T::Dependant; $n];
         })*
     }
 }
@@ -0,0 +1,210 @@
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::hash::{Hash, Hasher};
+use std::collections::HashMap;
+
+use crate::{Dependant, IntoStorage, Zc};
+
+/// Implemented for [`Dependant`] types that provide a key borrowed from their
+/// owner, for use with [`ZcIndexMap`].
+///
+/// The key returned has the lifetime of the owner (`'o`) and not of the borrow
+/// of the dependant, so it can only reference data within the owner (or
+/// `'static` data) and not the dependant itself.
+pub trait IndexKey<'o>: Dependant<'o> {
+    /// The type of key.
+    type Key: ?Sized + Hash + Eq;
+
+    /// Returns the key borrowed from the owner.
+    fn index_key(&self) -> &'o Self::Key;
+}
+
+/// Map of [`Zc`] entries keyed by data borrowed from each entry's own owner.
+///
+/// The key of each entry is projected from its dependant via [`IndexKey`]. As
+/// the storage of a [`Zc`] is stable, the key can reference the owner directly
+/// and does not need to be duplicated. The iteration order is arbitrary.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, IndexKey, Zc, ZcIndexMap};
+///
+/// #[derive(Dependant)]
+/// pub struct Document<'a> {
+///     name: &'a str,
+///     body: &'a str,
+/// }
+///
+/// fn parse(s: &str) -> Document<'_> {
+///     let (name, body) = s.split_at(s.find(':').unwrap());
+///     Document { name, body: &body[1..] }
+/// }
+///
+/// impl<'a> IndexKey<'a> for Document<'a> {
+///     type Key = str;
+///
+///     fn index_key(&self) -> &'a str {
+///         self.name
+///     }
+/// }
+///
+/// let mut map = ZcIndexMap::new();
+///
+/// let _ = map.insert(Zc::new(String::from("a:hello"), parse));
+/// let _ = map.insert(Zc::new(String::from("b:world"), parse));
+///
+/// assert_eq!(map.get("a").unwrap().get::<Document>().body, "hello");
+/// assert_eq!(map.remove("b").unwrap().into_owner(), "b:world");
+/// assert_eq!(map.len(), 1);
+/// ```
+pub struct ZcIndexMap<O, D>
+where
+    O: IntoStorage,
+    D: IndexKey<'static>,
+{
+    entries: HashMap<EntryKey<D::Key>, Zc<O, D>>,
+}
+
+impl<O, D> ZcIndexMap<O, D>
+where
+    O: IntoStorage,
+    D: IndexKey<'static>,
+{
+    /// Construct a new empty map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the map contains an entry for the key.
+    pub fn contains_key(&self, key: &D::Key) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns a reference to the entry for the key.
+    pub fn get(&self, key: &D::Key) -> Option<&Zc<O, D>> {
+        self.entries.get(key)
+    }
+
+    /// Inserts an entry into the map, keyed by its projected key.
+    ///
+    /// If the map already had an entry with the same key, it is replaced and
+    /// returned.
+    pub fn insert(&mut self, zc: Zc<O, D>) -> Option<Zc<O, D>> {
+        let key = Self::key_of(&zc);
+        // The existing key must be removed with its entry as it references the
+        // existing entry's owner, which `HashMap::insert` would retain.
+        let existing = self.entries.remove(key.get());
+        let _ = self.entries.insert(key, zc);
+        existing
+    }
+
+    /// Removes and returns the entry for the key.
+    pub fn remove(&mut self, key: &D::Key) -> Option<Zc<O, D>> {
+        self.entries.remove(key)
+    }
+
+    /// Removes all entries from the map.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns an iterator over the keys and entries of the map.
+    pub fn iter(&self) -> impl Iterator<Item = (&D::Key, &Zc<O, D>)> {
+        self.entries.iter().map(|(key, zc)| (key.get(), zc))
+    }
+
+    /// Returns an iterator over the keys of the map.
+    pub fn keys(&self) -> impl Iterator<Item = &D::Key> {
+        self.entries.keys().map(EntryKey::get)
+    }
+
+    /// Returns an iterator over the entries of the map.
+    pub fn values(&self) -> impl Iterator<Item = &Zc<O, D>> {
+        self.entries.values()
+    }
+
+    /// Consumes the map into an iterator over its entries.
+    pub fn into_values(self) -> impl Iterator<Item = Zc<O, D>> {
+        self.entries.into_values()
+    }
+
+    fn key_of(zc: &Zc<O, D>) -> EntryKey<D::Key> {
+        // The key can only reference data within the owner, which is stable
+        // while the entry exists.
+        EntryKey(zc.value.index_key())
+    }
+}
+
+impl<O, D> Default for ZcIndexMap<O, D>
+where
+    O: IntoStorage,
+    D: IndexKey<'static>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<O, D> Debug for ZcIndexMap<O, D>
+where
+    O: IntoStorage,
+    D: IndexKey<'static>,
+    D::Key: Debug,
+    Zc<O, D>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(key, zc)| (key.get(), zc)))
+            .finish()
+    }
+}
+
+/// Key referencing data within the owner of the entry it is stored with.
+struct EntryKey<K: ?Sized>(*const K);
+
+impl<K: ?Sized> EntryKey<K> {
+    fn get(&self) -> &K {
+        // SAFETY: The key is only ever stored along with the entry whose owner
+        // it references.
+        unsafe { &*self.0 }
+    }
+}
+
+impl<K: ?Sized + Hash> Hash for EntryKey<K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get().hash(state);
+    }
+}
+
+impl<K: ?Sized + PartialEq> PartialEq for EntryKey<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get() == other.get()
+    }
+}
+
+impl<K: ?Sized + Eq> Eq for EntryKey<K> {}
+
+impl<K: ?Sized> Borrow<K> for EntryKey<K> {
+    fn borrow(&self) -> &K {
+        self.get()
+    }
+}
+
+// SAFETY: The key is a shared reference into the owner of the entry.
+unsafe impl<K: ?Sized + Sync> Send for EntryKey<K> {}
+unsafe impl<K: ?Sized + Sync> Sync for EntryKey<K> {}
@@ -0,0 +1,222 @@
+//! Append-only string interning with stable storage.
+//!
+//! An [`Interner`] is an owner whose interned strings are never moved once
+//! added, so the `&'o str` symbols it hands out can be used within a
+//! [`Dependant`] and packaged along with it in a single [`Zc`].
+//!
+//! # Example
+//! ```
+//! use zc::intern::Interner;
+//! use zc::Zc;
+//!
+//! fn tokenize(interner: &Interner<String>) -> Vec<&str> {
+//!     interner
+//!         .source()
+//!         .split_whitespace()
+//!         .map(|word| interner.intern(word))
+//!         .collect()
+//! }
+//!
+//! let interner = Interner::with_source(String::from("a b a c b"));
+//! let data = Zc::new(interner, tokenize);
+//! let tokens = data.get::<Vec<&str>>();
+//!
+//! assert_eq!(tokens, &["a", "b", "a", "c", "b"]);
+//! // Equal symbols are deduplicated.
+//! assert!(core::ptr::eq(tokens[0], tokens[2]));
+//! assert_eq!(data.as_owned().len(), 3);
+//! ```
+//!
+//! [`Dependant`]: crate::Dependant
+//! [`Zc`]: crate::Zc
+
+use core::cell::RefCell;
+use core::fmt::{self, Debug};
+use core::ops::Range;
+use core::{slice, str};
+use std::collections::HashSet;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use aliasable::boxed::AliasableBox;
+
+use crate::verify::address_range;
+use crate::{Extents, FromStorage, IntoStorage};
+
+/// The default capacity in bytes of each chunk of interned strings.
+pub const DEFAULT_CHUNK_CAPACITY: usize = 4096;
+
+/// Append-only string interner with stable storage.
+///
+/// Strings are copied into chunks that are never reallocated, so a symbol
+/// returned from [`Interner::intern()`] remains valid for as long as the
+/// interner. Equal strings are deduplicated and return the same symbol.
+///
+/// The interner can optionally own a source `S` (eg. the input being parsed)
+/// that can be accessed along with it when constructing a [`Dependant`].
+///
+/// As interning is done via a shared reference, the interner is not `Sync`.
+///
+/// [`Dependant`]: crate::Dependant
+pub struct Interner<S = ()> {
+    source: S,
+    state: RefCell<State>,
+}
+
+struct State {
+    chunk_capacity: usize,
+    chunks: Vec<String>,
+    symbols: HashSet<&'static str>,
+}
+
+impl Interner {
+    /// Construct a new empty `Interner`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_source(())
+    }
+}
+
+impl<S> Interner<S> {
+    /// Construct a new empty `Interner` given a source.
+    pub fn with_source(source: S) -> Self {
+        Self::with_source_and_chunk_capacity(source, DEFAULT_CHUNK_CAPACITY)
+    }
+
+    /// Construct a new empty `Interner` given a source and the capacity in
+    /// bytes of each chunk of interned strings.
+    ///
+    /// Strings longer than the chunk capacity are stored in a chunk of their
+    /// own.
+    pub fn with_source_and_chunk_capacity(source: S, chunk_capacity: usize) -> Self {
+        Self {
+            source,
+            state: RefCell::new(State {
+                chunk_capacity,
+                chunks: Vec::new(),
+                symbols: HashSet::new(),
+            }),
+        }
+    }
+
+    /// Returns a reference to the source.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Consumes `self` into the source, dropping all interned strings.
+    pub fn into_source(self) -> S {
+        self.source
+    }
+
+    /// Returns the number of unique strings interned.
+    pub fn len(&self) -> usize {
+        self.state.borrow().symbols.len()
+    }
+
+    /// Returns `true` if no strings have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the symbol for the string if it has been interned.
+    pub fn get(&self, s: &str) -> Option<&str> {
+        self.state.borrow().symbols.get(s).copied()
+    }
+
+    /// Interns the string, returning its symbol.
+    ///
+    /// If an equal string has already been interned, its symbol is returned
+    /// instead.
+    pub fn intern(&self, s: &str) -> &str {
+        let mut state = self.state.borrow_mut();
+        if let Some(symbol) = state.symbols.get(s) {
+            return symbol;
+        }
+        let symbol = state.push(s);
+        let _ = state.symbols.insert(symbol);
+        symbol
+    }
+}
+
+impl State {
+    /// Copies the string into a chunk, returning a reference to it with its
+    /// lifetime erased.
+    fn push(&mut self, s: &str) -> &'static str {
+        let chunk_capacity = self.chunk_capacity;
+        let fits_last = self
+            .chunks
+            .last()
+            .is_some_and(|chunk| chunk.capacity() - chunk.len() >= s.len());
+        let chunk = if fits_last {
+            self.chunks.last_mut().unwrap()
+        } else if s.len() > chunk_capacity {
+            // Store the string in its own chunk, before the last so the space
+            // remaining in the last can still be used.
+            let index = self.chunks.len().saturating_sub(1);
+            self.chunks.insert(index, String::with_capacity(s.len()));
+            &mut self.chunks[index]
+        } else {
+            self.chunks.push(String::with_capacity(chunk_capacity));
+            self.chunks.last_mut().unwrap()
+        };
+        let start = chunk.len();
+        // Never reallocates as the chunk has the capacity for the string.
+        chunk.push_str(s);
+        // SAFETY: The bytes of the chunk are valid UTF-8 and never moved or
+        // modified once pushed, until the interner is dropped.
+        unsafe {
+            let bytes = slice::from_raw_parts(chunk.as_ptr().add(start), s.len());
+            str::from_utf8_unchecked(bytes)
+        }
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Debug for Interner<S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interner")
+            .field("source", &self.source)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> IntoStorage for Interner<S>
+where
+    S: 'static,
+{
+    type Storage = AliasableBox<Self>;
+
+    fn into_storage(self) -> Self::Storage {
+        AliasableBox::from_unique(alloc::boxed::Box::new(self))
+    }
+}
+
+impl<S> FromStorage for Interner<S>
+where
+    S: 'static,
+{
+    fn from_storage(storage: Self::Storage) -> Self {
+        *AliasableBox::into_unique(storage)
+    }
+}
+
+impl<S> Extents for Interner<S> {
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self));
+        let state = self.state.borrow();
+        for chunk in &state.chunks {
+            visit(address_range(chunk.as_str()));
+        }
+    }
+}
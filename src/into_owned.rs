@@ -0,0 +1,354 @@
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use core::ops::Deref;
+
+use crate::{Dependant, Extents, IntoStorage, Rebind, References, Zc};
+
+/// Implemented for types that can be converted into a counterpart that owns
+/// all of the data it references.
+///
+/// Used to detach a [`Dependant`] from its owner with
+/// [`Zc::into_owned_dependant()`], so a small view can outlive a large owner.
+///
+/// Can be derived with `#[derive(IntoOwned)]`, which generates an owned
+/// counterpart of the type named with an `Owned` suffix, with each field
+/// converted via `IntoOwned`. Derives for the owned type can be requested with
+/// `#[into_owned(derive(..))]`.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, IntoOwned, Zc};
+///
+/// #[derive(Dependant, IntoOwned)]
+/// #[into_owned(derive(Debug, PartialEq))]
+/// pub struct Header<'a> {
+///     name: &'a str,
+///     values: Vec<&'a [u8]>,
+/// }
+///
+/// fn parse(source: &str) -> Header<'_> {
+///     let (name, values) = source.split_once(':').unwrap();
+///     Header {
+///         name,
+///         values: values.split(',').map(str::as_bytes).collect(),
+///     }
+/// }
+///
+/// let data = Zc::new(String::from("accept:a,b"), parse);
+///
+/// assert_eq!(
+///     data.into_owned_dependant(),
+///     HeaderOwned {
+///         name: String::from("accept"),
+///         values: vec![b"a".to_vec(), b"b".to_vec()],
+///     }
+/// );
+/// ```
+pub trait IntoOwned {
+    /// The owned counterpart of `Self`.
+    type Owned;
+
+    /// Converts `self` into its owned counterpart.
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<O, D> Zc<O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Consumes `self`, converting the [`Dependant`] into its owned
+    /// counterpart and dropping the owner.
+    pub fn into_owned_dependant<T>(self) -> T
+    where
+        D: for<'a> Rebind<'a>,
+        for<'a> <D as Rebind<'a>>::Dependant: IntoOwned<Owned = T>,
+    {
+        let Self { value, storage } = self;
+        // SAFETY: The owned counterpart cannot reference the storage, as it is
+        // the same type for any lifetime the dependant is rebound to.
+        let owned = unsafe { into_owned_rebound(&*storage, value) };
+        drop(storage);
+        owned
+    }
+
+    /// Detaches the [`Dependant`] from the owner if the owner is at least
+    /// `ratio` times larger than the data the dependant references, otherwise
+    /// returning `self`.
+    ///
+    /// The size of the owner is the sum of the [`Extents`] of the target of its
+    /// storage (the target along with any data it owns outside of itself).
+    /// The size referenced is the sum of each reference visited, which
+    /// approximates the size of the data copied by
+    /// [`Zc::into_owned_dependant()`].
+    ///
+    /// # Errors
+    /// Returns `self` if the owner is not large enough to detach from.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::Zc;
+    ///
+    /// fn header(body: &[u8]) -> &[u8] {
+    ///     &body[..16]
+    /// }
+    ///
+    /// let data = Zc::new(vec![0u8; 1024], header);
+    /// let data = data.detach_if(128).unwrap_err();
+    /// let header: Vec<u8> = data.detach_if(32).unwrap();
+    ///
+    /// assert_eq!(header.len(), 16);
+    /// ```
+    pub fn detach_if<T>(self, ratio: usize) -> Result<T, Self>
+    where
+        D: References + for<'a> Rebind<'a>,
+        for<'a> <D as Rebind<'a>>::Dependant: IntoOwned<Owned = T>,
+        <O::Storage as Deref>::Target: Extents,
+    {
+        let mut owner_len = 0_usize;
+        let target = &*self.storage;
+        target.visit_extents(&mut |extent| owner_len += extent.len());
+        let mut referenced_len = 0_usize;
+        self.value
+            .visit_references(&mut |reference| referenced_len += reference.len());
+        if referenced_len.saturating_mul(ratio) <= owner_len {
+            Ok(self.into_owned_dependant())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+unsafe fn into_owned_rebound<'a, T, D>(
+    _target: &'a T,
+    value: D,
+) -> <D::Dependant as IntoOwned>::Owned
+where
+    T: ?Sized,
+    D: Rebind<'a>,
+    D::Dependant: IntoOwned,
+{
+    crate::private::rebind(value).into_owned()
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// IntoOwned impl
+
+macro_rules! impl_into_owned_self {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoOwned for $ty {
+                type Owned = $ty;
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_into_owned_self!((), bool, char, f32, f64, isize, usize);
+impl_into_owned_self!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl_into_owned_self!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+);
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned, E: IntoOwned> IntoOwned for Result<T, E> {
+    type Owned = Result<T::Owned, E::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        match self {
+            Ok(value) => Ok(value.into_owned()),
+            Err(err) => Err(err.into_owned()),
+        }
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Wrapping<T> {
+    type Owned = Wrapping<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Wrapping(self.0.into_owned())
+    }
+}
+
+impl<T: IntoOwned, const N: usize> IntoOwned for [T; N] {
+    type Owned = [T::Owned; N];
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+macro_rules! impl_into_owned_tuple {
+    ($($field:tt $ty:ident),+) => {
+        impl<$($ty: IntoOwned),+> IntoOwned for ($($ty,)+) {
+            type Owned = ($($ty::Owned,)+);
+
+            fn into_owned(self) -> Self::Owned {
+                ($(self.$field.into_owned(),)+)
+            }
+        }
+    };
+}
+
+impl_into_owned_tuple!(0 T1);
+impl_into_owned_tuple!(0 T1, 1 T2);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3, 3 T4);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7);
+impl_into_owned_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8);
+
+#[cfg(feature = "alloc")]
+mod alloc {
+    use alloc::borrow::{Cow, ToOwned};
+    use alloc::boxed::Box;
+    use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::IntoOwned;
+
+    impl_into_owned_self!(String);
+
+    impl<T: ?Sized + ToOwned> IntoOwned for &T {
+        type Owned = T::Owned;
+
+        fn into_owned(self) -> Self::Owned {
+            self.to_owned()
+        }
+    }
+
+    impl<T: ?Sized + ToOwned> IntoOwned for &mut T {
+        type Owned = T::Owned;
+
+        fn into_owned(self) -> Self::Owned {
+            (*self).to_owned()
+        }
+    }
+
+    impl<T: ?Sized + ToOwned> IntoOwned for Cow<'_, T> {
+        type Owned = <T as ToOwned>::Owned;
+
+        fn into_owned(self) -> <T as ToOwned>::Owned {
+            Cow::into_owned(self)
+        }
+    }
+
+    impl<T: IntoOwned> IntoOwned for Box<T> {
+        type Owned = Box<T::Owned>;
+
+        fn into_owned(self) -> Self::Owned {
+            Box::new((*self).into_owned())
+        }
+    }
+
+    impl<T: IntoOwned> IntoOwned for Vec<T> {
+        type Owned = Vec<T::Owned>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter().map(IntoOwned::into_owned).collect()
+        }
+    }
+
+    impl<T> IntoOwned for BTreeSet<T>
+    where
+        T: IntoOwned,
+        T::Owned: Ord,
+    {
+        type Owned = BTreeSet<T::Owned>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter().map(IntoOwned::into_owned).collect()
+        }
+    }
+
+    impl<T> IntoOwned for BinaryHeap<T>
+    where
+        T: IntoOwned,
+        T::Owned: Ord,
+    {
+        type Owned = BinaryHeap<T::Owned>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter().map(IntoOwned::into_owned).collect()
+        }
+    }
+
+    impl<K, V> IntoOwned for BTreeMap<K, V>
+    where
+        K: IntoOwned,
+        K::Owned: Ord,
+        V: IntoOwned,
+    {
+        type Owned = BTreeMap<K::Owned, V::Owned>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std {
+    use core::hash::{BuildHasher, Hash};
+    use std::collections::{HashMap, HashSet};
+
+    use super::IntoOwned;
+
+    impl<T, S> IntoOwned for HashSet<T, S>
+    where
+        T: IntoOwned,
+        T::Owned: Eq + Hash,
+        S: BuildHasher + Default,
+    {
+        type Owned = HashSet<T::Owned, S>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter().map(IntoOwned::into_owned).collect()
+        }
+    }
+
+    impl<K, V, S> IntoOwned for HashMap<K, V, S>
+    where
+        K: IntoOwned,
+        K::Owned: Eq + Hash,
+        V: IntoOwned,
+        S: BuildHasher + Default,
+    {
+        type Owned = HashMap<K::Owned, V::Owned, S>;
+
+        fn into_owned(self) -> Self::Owned {
+            self.into_iter()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Reading [`Zc`] values from [`Read`] and [`BufRead`] sources.
+//!
+//! [`Zc::from_reader()`] reads a whole source into a single owner, while
+//! [`Records`] splits a source into records on a delimiter or length prefix,
+//! yielding a `Zc` per record. Records are read into owners recycled via
+//! [`Records::recycle()`], so a large source can be processed without
+//! allocating per record.
+//!
+//! # Example
+//! ```
+//! use zc::io::{RecordError, Records};
+//! use zc::Dependant;
+//!
+//! #[derive(Dependant)]
+//! pub struct Row<'a> {
+//!     id: &'a [u8],
+//!     name: &'a [u8],
+//! }
+//!
+//! fn parse(bytes: &[u8]) -> Result<Row<'_>, &'static str> {
+//!     let mut fields = bytes.splitn(2, |b| *b == b',');
+//!     match (fields.next(), fields.next()) {
+//!         (Some(id), Some(name)) => Ok(Row { id, name }),
+//!         _ => Err("expected id and name"),
+//!     }
+//! }
+//!
+//! let export = &b"1,alice\ninvalid\n2,bob\n"[..];
+//! let mut records = Records::delimited(export, b'\n', parse);
+//! let mut names = Vec::new();
+//!
+//! while let Some(record) = records.next() {
+//!     match record {
+//!         Ok(row) => {
+//!             names.push(String::from_utf8_lossy(row.get::<Row>().name).into_owned());
+//!             records.recycle(row.into_owner());
+//!         }
+//!         Err(RecordError::Construct(err, bytes)) => {
+//!             assert_eq!((err, &bytes[..]), ("expected id and name", &b"invalid"[..]));
+//!             records.recycle(bytes);
+//!         }
+//!         Err(RecordError::Io(err)) => panic!("{}", err),
+//!     }
+//! }
+//!
+//! assert_eq!(names, ["alice", "bob"]);
+//! ```
+
+use core::convert::TryFrom;
+use core::fmt::{self, Debug, Display};
+use core::iter::FusedIterator;
+use std::io::{self, BufRead, ErrorKind, Read};
+
+use alloc::vec::Vec;
+
+use crate::private::{Construct, TryConstruct};
+use crate::{Dependant, Zc};
+
+impl<D> Zc<Vec<u8>, D>
+where
+    D: Dependant<'static>,
+{
+    /// Construct a new zero-copied structure given a reader, read to the end
+    /// into a `Vec<u8>` owner, and a function for constructing the
+    /// [`Dependant`].
+    ///
+    /// # Example
+    /// ```
+    /// use zc::Zc;
+    ///
+    /// fn first_word(bytes: &[u8]) -> &[u8] {
+    ///     bytes.split(|b| *b == b' ').next().unwrap()
+    /// }
+    ///
+    /// let data = Zc::from_reader(&b"hello world"[..], first_word).unwrap();
+    ///
+    /// assert_eq!(data.get::<&[u8]>(), b"hello");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if reading failed.
+    pub fn from_reader<R, C>(mut reader: R, constructor: C) -> io::Result<Self>
+    where
+        R: Read,
+        C: for<'o> Construct<'o, [u8], Dependant = D>,
+    {
+        let mut owner = Vec::new();
+        let _ = reader.read_to_end(&mut owner)?;
+        Ok(Self::new(owner, constructor))
+    }
+}
+
+/// Width and byte order of the length prefixing each record read by
+/// [`Records::length_prefixed()`].
+///
+/// The length does not include the prefix itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthPrefix {
+    /// A single byte.
+    U8,
+    /// Two bytes, big endian.
+    U16Be,
+    /// Two bytes, little endian.
+    U16Le,
+    /// Four bytes, big endian.
+    U32Be,
+    /// Four bytes, little endian.
+    U32Le,
+    /// Eight bytes, big endian.
+    U64Be,
+    /// Eight bytes, little endian.
+    U64Le,
+}
+
+impl LengthPrefix {
+    /// Returns the number of bytes of the prefix.
+    #[must_use]
+    pub fn width(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16Be | Self::U16Le => 2,
+            Self::U32Be | Self::U32Le => 4,
+            Self::U64Be | Self::U64Le => 8,
+        }
+    }
+
+    pub(crate) fn decode(self, prefix: &[u8]) -> u64 {
+        let mut bytes = [0; 8];
+        match self {
+            Self::U8 | Self::U16Le | Self::U32Le | Self::U64Le => {
+                bytes[..prefix.len()].copy_from_slice(prefix);
+                u64::from_le_bytes(bytes)
+            }
+            Self::U16Be | Self::U32Be | Self::U64Be => {
+                bytes[8 - prefix.len()..].copy_from_slice(prefix);
+                u64::from_be_bytes(bytes)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Framing {
+    Delimiter(u8),
+    LengthPrefix(LengthPrefix),
+}
+
+/// Iterator over the records of a [`BufRead`], yielding a [`Zc`] with a
+/// `Vec<u8>` owner per record.
+///
+/// Each record is read into an owner taken from a pool of owners returned via
+/// [`Records::recycle()`], or a new owner if the pool is empty. Records that
+/// fail to be constructed are returned along with their bytes, so they can be
+/// reported or recycled. The iterator ends after the first I/O error.
+///
+/// See the [`io`](crate::io) module for an example.
+pub struct Records<R, C> {
+    reader: R,
+    constructor: C,
+    framing: Framing,
+    pool: Vec<Vec<u8>>,
+    done: bool,
+}
+
+impl<R, C> Records<R, C>
+where
+    R: BufRead,
+{
+    /// Construct a new `Records` splitting on a delimiter, which is not
+    /// included in the records.
+    ///
+    /// A final record without a trailing delimiter is still yielded.
+    pub fn delimited(reader: R, delimiter: u8, constructor: C) -> Self {
+        Self::new(reader, Framing::Delimiter(delimiter), constructor)
+    }
+
+    /// Construct a new `Records` reading records each prefixed with their
+    /// length.
+    ///
+    /// Records are read incrementally, so a corrupt length fails with
+    /// [`ErrorKind::UnexpectedEof`] at the end of the source instead of
+    /// allocating the length up front.
+    pub fn length_prefixed(reader: R, prefix: LengthPrefix, constructor: C) -> Self {
+        Self::new(reader, Framing::LengthPrefix(prefix), constructor)
+    }
+
+    fn new(reader: R, framing: Framing, constructor: C) -> Self {
+        Self {
+            reader,
+            constructor,
+            framing,
+            pool: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// Returns an owner to the pool to read a later record into.
+    ///
+    /// The owner is cleared, keeping its capacity.
+    pub fn recycle(&mut self, mut owner: Vec<u8>) {
+        owner.clear();
+        self.pool.push(owner);
+    }
+
+    /// Returns the number of owners in the pool.
+    pub fn pooled(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Consumes `self`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Returns `None` if the source ended at a record boundary.
+    fn read_record(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut owner = self.pool.pop().unwrap_or_default();
+        let read = match self.framing {
+            Framing::Delimiter(delimiter) => {
+                let read = self.reader.read_until(delimiter, &mut owner)? != 0;
+                if owner.last() == Some(&delimiter) {
+                    let _ = owner.pop();
+                }
+                read
+            }
+            Framing::LengthPrefix(prefix) => self.read_length_prefixed(prefix, &mut owner)?,
+        };
+        if read {
+            Ok(Some(owner))
+        } else {
+            self.pool.push(owner);
+            Ok(None)
+        }
+    }
+
+    fn read_length_prefixed(
+        &mut self,
+        prefix: LengthPrefix,
+        owner: &mut Vec<u8>,
+    ) -> io::Result<bool> {
+        if self.reader.fill_buf()?.is_empty() {
+            return Ok(false);
+        }
+        let mut bytes = [0; 8];
+        let bytes = &mut bytes[..prefix.width()];
+        self.reader.read_exact(bytes)?;
+        let len = prefix.decode(bytes);
+        let expected = usize::try_from(len)
+            .map_err(|_| io::Error::new(ErrorKind::InvalidData, "record length exceeds usize"))?;
+        if (&mut self.reader).take(len).read_to_end(owner)? < expected {
+            return Err(io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "source ended within a record",
+            ));
+        }
+        Ok(true)
+    }
+}
+
+impl<R, C, D, E> Iterator for Records<R, C>
+where
+    R: BufRead,
+    D: Dependant<'static>,
+    E: 'static,
+    C: for<'o> TryConstruct<'o, [u8], Error = E, Dependant = D> + Clone,
+{
+    type Item = Result<Zc<Vec<u8>, D>, RecordError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(owner)) => Some(
+                Zc::try_new(owner, self.constructor.clone())
+                    .map_err(|(err, owner)| RecordError::Construct(err, owner)),
+            ),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(RecordError::Io(err)))
+            }
+        }
+    }
+}
+
+impl<R, C, D, E> FusedIterator for Records<R, C>
+where
+    R: BufRead,
+    D: Dependant<'static>,
+    E: 'static,
+    C: for<'o> TryConstruct<'o, [u8], Error = E, Dependant = D> + Clone,
+{
+}
+
+impl<R, C> Debug for Records<R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Records")
+            .field("framing", &self.framing)
+            .field("pooled", &self.pool.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error yielded from [`Records`].
+#[derive(Debug)]
+pub enum RecordError<E> {
+    /// The source failed to be read.
+    Io(io::Error),
+    /// The [`Dependant`] failed to be constructed from the record, returned
+    /// along with its bytes.
+    ///
+    /// [`Dependant`]: crate::Dependant
+    Construct(E, Vec<u8>),
+}
+
+impl<E> From<io::Error> for RecordError<E> {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl<E: Display> Display for RecordError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read record: {err}"),
+            Self::Construct(err, bytes) => {
+                write!(
+                    f,
+                    "failed to construct dependant from {} byte record: {err}",
+                    bytes.len()
+                )
+            }
+        }
+    }
+}
+
+impl<E> std::error::Error for RecordError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Construct(err, _) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use core::fmt::{self, Debug};
+use core::ops::Range;
+use std::sync::OnceLock;
+
+use crate::verify::address_range;
+use crate::{Dependant, Rebind, References};
+
+/// Lazily computed [`Dependant`] field with once-cell semantics.
+///
+/// The value is computed from the input on first access via a fixed
+/// initializer. As the initializer is a function pointer and the input is
+/// borrowed from the same owner, the interior mutability can only ever store a
+/// value produced from the owner, making it a valid [`Dependant`].
+///
+/// `I` is the input type, defaulting to `[u8]`.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, LazyField, Zc};
+///
+/// #[derive(Dependant)]
+/// pub struct Document<'a> {
+///     header: &'a [u8],
+///     sections: LazyField<'a, Vec<&'a [u8]>>,
+/// }
+///
+/// fn split_sections(body: &[u8]) -> Vec<&[u8]> {
+///     body.split(|b| *b == b';').collect()
+/// }
+///
+/// fn parse(bytes: &[u8]) -> Document<'_> {
+///     Document {
+///         header: &bytes[..1],
+///         sections: LazyField::new(&bytes[1..], split_sections),
+///     }
+/// }
+///
+/// let data = Zc::new(b"#a;b;c".to_vec(), parse);
+/// let document = data.get::<Document>();
+///
+/// assert_eq!(document.header, b"#");
+/// assert_eq!(document.sections.get(), &[b"a", b"b", b"c"]);
+/// ```
+pub struct LazyField<'o, T, I: ?Sized = [u8]> {
+    input: &'o I,
+    init: fn(&'o I) -> T,
+    value: OnceLock<T>,
+}
+
+impl<'o, T, I> LazyField<'o, T, I>
+where
+    I: ?Sized,
+{
+    /// Construct a new `LazyField` given the input and the initializer to
+    /// compute the value from it.
+    pub fn new(input: &'o I, init: fn(&'o I) -> T) -> Self {
+        Self {
+            input,
+            init,
+            value: OnceLock::new(),
+        }
+    }
+
+    /// Returns the input the value is computed from.
+    pub fn input(&self) -> &'o I {
+        self.input
+    }
+
+    /// Returns the value, computing it if not already.
+    pub fn get(&self) -> &T {
+        self.value.get_or_init(|| (self.init)(self.input))
+    }
+
+    /// Returns the value if it has already been computed.
+    pub fn get_if_init(&self) -> Option<&T> {
+        self.value.get()
+    }
+}
+
+impl<T, I> Debug for LazyField<'_, T, I>
+where
+    T: Debug,
+    I: ?Sized + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyField")
+            .field("input", &self.input)
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
+
+// SAFETY: The only value that can be stored is produced by the fixed
+// initializer from the input, both of which are provided at construction with
+// the lifetime `'o`.
+unsafe impl<'o, T, I> Dependant<'o> for LazyField<'o, T, I>
+where
+    T: Dependant<'o>,
+    I: ?Sized + 'static,
+{
+    type Static = LazyField<'static, T::Static, I>;
+}
+
+unsafe impl<'o, T, I> Rebind<'o> for LazyField<'static, T, I>
+where
+    T: Rebind<'o>,
+    I: ?Sized + 'static,
+{
+    type Dependant = LazyField<'o, T::Dependant, I>;
+}
+
+impl<T, I> References for LazyField<'_, T, I>
+where
+    T: References,
+    I: ?Sized,
+{
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self.input));
+        if let Some(value) = self.value.get() {
+            value.visit_references(visit);
+        }
+    }
+}
@@ -22,24 +22,93 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod arena;
+#[cfg(feature = "alloc")]
+mod buffer;
+#[cfg(feature = "bump")]
+mod bump;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "codec")]
+mod codec;
+#[cfg(feature = "alloc")]
+mod debug_view;
+mod future;
 mod r#impl;
+#[cfg(feature = "std")]
+mod index_map;
+#[cfg(feature = "std")]
+pub mod intern;
+mod into_owned;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "std")]
+mod lazy;
 // FIXME: Remove the need for macros.
 mod macros;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "alloc")]
+mod owner;
 mod private;
+#[cfg(feature = "reload")]
+mod reload;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "alloc")]
+pub mod text;
+mod verify;
+mod zc_mut;
 
 use core::fmt::{self, Debug, Display};
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut, Range};
 
 #[cfg(feature = "alloc")]
 pub use aliasable;
+#[cfg(feature = "bump")]
+pub use bumpalo;
 
 #[cfg(feature = "derive")]
-pub use zc_derive::Dependant;
+pub use zc_derive::{Dependant, IntoOwned, References};
+
+#[cfg(feature = "alloc")]
+pub use self::arena::{Owners, ZcArena};
+#[cfg(feature = "alloc")]
+pub use self::buffer::{Chunks, ZcBuffer};
+#[cfg(feature = "bump")]
+pub use self::bump::BumpOwner;
+#[cfg(feature = "std")]
+pub use self::cache::ZcCache;
+#[cfg(feature = "codec")]
+pub use self::codec::{CodecError, ZcCodec};
+#[cfg(feature = "alloc")]
+pub use self::debug_view::DebugView;
+#[cfg(feature = "std")]
+pub use self::index_map::{IndexKey, ZcIndexMap};
+pub use self::into_owned::IntoOwned;
+#[cfg(feature = "std")]
+pub use self::lazy::LazyField;
+#[cfg(feature = "alloc")]
+pub use self::owner::{BoxedOwner, DynOwner};
+#[cfg(feature = "reload")]
+pub use self::reload::{ReloadError, ZcReloader};
+#[cfg(feature = "alloc")]
+pub use self::shared::{ZcBytes, ZcStr};
+pub use self::verify::{Extents, References, VerifyError};
+pub use self::zc_mut::ZcMut;
 
 use self::private::{Construct, TryConstruct};
 
-/// Zero-copy structure consisting of an [`Owner`] and a [`Dependant`].
-pub struct Zc<O: Owner, D> {
+#[cfg(feature = "alloc")]
+pub(crate) type Target<O> = <<O as IntoStorage>::Storage as Deref>::Target;
+
+/// Zero-copy structure consisting of an owner and a [`Dependant`].
+pub struct Zc<O: IntoStorage, D> {
     // SAFETY: Order of fields is important for preventing dropping the storage
     // before the value that references it.
     value: D,
@@ -48,10 +117,10 @@ pub struct Zc<O: Owner, D> {
 
 impl<O, D> Zc<O, D>
 where
-    O: Owner,
+    O: IntoStorage,
     D: Dependant<'static>,
 {
-    /// Construct a new zero-copied structure given an [`Owner`] and a
+    /// Construct a new zero-copied structure given an owner and a
     /// function for constructing the [`Dependant`].
     ///
     /// # Example
@@ -74,16 +143,19 @@ where
     where
         C: for<'o> Construct<'o, <O::Storage as Deref>::Target, Dependant = D>,
     {
-        let storage = Owner::into_storage(owner);
+        let storage = IntoStorage::into_storage(owner);
         // Create a temporary dependant given the target reference.
-        let value = unsafe { constructor.construct(storage.deref()) };
+        let value = unsafe { constructor.construct(&*storage) };
         // Construct the zero-copy structure given the raw parts.
-        Self { storage, value }
+        Self::from_raw_parts(value, storage)
     }
 
-    /// Try construct a new zero-copied structure given an [`Owner`] and a
+    /// Try construct a new zero-copied structure given an owner and a
     /// function for constructing the [`Dependant`].
     ///
+    /// Along with [`IntoStorage`], the owner must implement [`FromStorage`] so
+    /// it can be returned if the constructor fails.
+    ///
     /// # Example
     /// ```
     /// use zc::{Zc, Dependant};
@@ -108,14 +180,114 @@ where
     /// Returns `E` if the constructor failed.
     pub fn try_new<C, E>(owner: O, constructor: C) -> Result<Self, (E, O)>
     where
+        O: FromStorage,
         E: 'static,
         C: for<'o> TryConstruct<'o, <O::Storage as Deref>::Target, Error = E, Dependant = D>,
     {
-        let storage = Owner::into_storage(owner);
+        Self::try_new_in_storage(owner, constructor)
+            .map_err(|(err, storage)| (err, FromStorage::from_storage(storage)))
+    }
+
+    // Same as `try_new()`, but returns the storage rather than the owner if
+    // the constructor failed, so the owner need not implement `FromStorage`.
+    pub(crate) fn try_new_in_storage<C, E>(
+        owner: O,
+        constructor: C,
+    ) -> Result<Self, (E, O::Storage)>
+    where
+        E: 'static,
+        C: for<'o> TryConstruct<'o, <O::Storage as Deref>::Target, Error = E, Dependant = D>,
+    {
+        let storage = IntoStorage::into_storage(owner);
         // Try create a temporary dependant given the target reference.
-        match unsafe { constructor.try_construct(storage.deref()) } {
-            Ok(value) => Ok(Self { storage, value }),
-            Err(err) => Err((err, Owner::from_storage(storage))),
+        match unsafe { constructor.try_construct(&*storage) } {
+            Ok(value) => Ok(Self::from_raw_parts(value, storage)),
+            Err(err) => Err((err, storage)),
+        }
+    }
+
+    /// Construct a new zero-copied structure given an owner with mutable
+    /// storage, a function for mutating the owned data in place and a
+    /// function for constructing the [`Dependant`].
+    ///
+    /// The storage of the owner must implement [`StorageMut`].
+    ///
+    /// The mutating function returns the part of the owned data to construct
+    /// the [`Dependant`] from, ending the mutable borrow before the shared
+    /// borrow begins.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::{Zc, Dependant};
+    ///
+    /// #[derive(Dependant)]
+    /// struct MyStruct<'a>(&'a [u8]);
+    ///
+    /// fn construct(bytes: &[u8]) -> MyStruct<'_> {
+    ///     MyStruct(bytes)
+    /// }
+    ///
+    /// let owner = vec![1, 2, 3];
+    /// let data = Zc::new_after_mut(
+    ///     owner,
+    ///     |bytes: &mut [u8]| {
+    ///         bytes.iter_mut().for_each(|b| *b ^= 0xFF);
+    ///         &bytes[1..]
+    ///     },
+    ///     construct,
+    /// );
+    ///
+    /// assert_eq!(data.get::<MyStruct>().0, &[0xFD, 0xFC]);
+    /// ```
+    pub fn new_after_mut<M, C>(owner: O, mutate: M, constructor: C) -> Self
+    where
+        O::Storage: StorageMut,
+        M: for<'o> FnOnce(
+            &'o mut <O::Storage as Deref>::Target,
+        ) -> &'o <O::Storage as Deref>::Target,
+        C: for<'o> Construct<'o, <O::Storage as Deref>::Target, Dependant = D>,
+    {
+        let mut storage = IntoStorage::into_storage(owner);
+        // Mutate the target and freeze it for constructing the dependant.
+        let frozen = mutate(&mut *storage);
+        // Create a temporary dependant given the frozen target reference.
+        let value = unsafe { constructor.construct(frozen) };
+        // Construct the zero-copy structure given the raw parts.
+        Self::from_raw_parts(value, storage)
+    }
+
+    /// Try construct a new zero-copied structure given an owner with mutable
+    /// storage, a function for mutating the owned data in place and a
+    /// function for constructing the [`Dependant`].
+    ///
+    /// Along with the storage implementing [`StorageMut`], the owner must
+    /// implement [`FromStorage`] so it can be returned if either function
+    /// fails. See [`Zc::new_after_mut()`].
+    ///
+    /// # Errors
+    /// Returns `E` if either the mutating function or the constructor failed,
+    /// along with the owner (including any mutations made).
+    pub fn try_new_after_mut<M, C, E>(owner: O, mutate: M, constructor: C) -> Result<Self, (E, O)>
+    where
+        O: FromStorage,
+        O::Storage: StorageMut,
+        E: 'static,
+        M: for<'o> FnOnce(
+            &'o mut <O::Storage as Deref>::Target,
+        ) -> Result<&'o <O::Storage as Deref>::Target, E>,
+        C: for<'o> TryConstruct<'o, <O::Storage as Deref>::Target, Error = E, Dependant = D>,
+    {
+        let mut storage = IntoStorage::into_storage(owner);
+        // Try mutate the target and freeze it for constructing the dependant,
+        // then try create a temporary dependant given the frozen target
+        // reference.
+        let result = match mutate(&mut *storage) {
+            Ok(frozen) => unsafe { constructor.try_construct(frozen) },
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(value) => Ok(Self::from_raw_parts(value, storage)),
+            Err(err) => Err((err, FromStorage::from_storage(storage))),
         }
     }
 
@@ -149,20 +321,25 @@ where
     // dependant again to retrieve it. GATs should provide us a way to make this
     // nicer with a generic associated lifetime.
     // See: https://github.com/rust-lang/rust/issues/44265
-    pub fn get<'a, T>(&'a self) -> &T
+    pub fn get<'a, T>(&'a self) -> &'a T
     where
         T: Dependant<'a, Static = D>,
     {
-        let value_ptr: *const D = &self.value;
+        let value_ptr: *const D = core::ptr::addr_of!(self.value);
         unsafe { &*value_ptr.cast::<T>() }
     }
+
+    #[inline]
+    fn from_raw_parts(value: D, storage: O::Storage) -> Self {
+        Self { value, storage }
+    }
 }
 
 impl<O, D> Zc<O, D>
 where
-    O: Owner,
+    O: IntoStorage,
 {
-    /// Return a reference to the data [`Owner`] provides.
+    /// Return a reference to the data the owner provides.
     ///
     /// # Example
     /// ```
@@ -183,10 +360,39 @@ where
     /// assert_eq!(data.as_owned(), &[1, 2, 3]);
     /// ```
     pub fn as_owned(&self) -> &<O::Storage as Deref>::Target {
-        &*self.storage
+        &self.storage
     }
 
-    /// Consumes `self` into the [`Owner`].
+    /// Returns the byte range of a slice within the data the owner provides,
+    /// or `None` if the slice does not lie within it.
+    ///
+    /// This is useful for recovering positions from slices held by the
+    /// [`Dependant`], such as those within parser errors.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::Zc;
+    ///
+    /// fn second_word(s: &str) -> &str {
+    ///     s.split(' ').nth(1).unwrap()
+    /// }
+    ///
+    /// let data = Zc::new(String::from("hello world"), second_word);
+    ///
+    /// assert_eq!(data.span_of(*data.get::<&str>()), Some(6..11));
+    /// assert_eq!(data.span_of("world"), None);
+    /// ```
+    pub fn span_of<S>(&self, sub: &S) -> Option<Range<usize>>
+    where
+        S: ?Sized + AsRef<[u8]>,
+        <O::Storage as Deref>::Target: AsRef<[u8]>,
+    {
+        private::span_within(self.as_owned().as_ref(), sub.as_ref())
+    }
+
+    /// Consumes `self` into the owner.
+    ///
+    /// Only available if the owner implements [`FromStorage`].
     ///
     /// # Example
     /// ```
@@ -206,8 +412,15 @@ where
     ///
     /// assert_eq!(data.into_owner(), vec![1, 2, 3]);
     /// ```
-    pub fn into_owner(self) -> O {
-        Owner::from_storage(self.storage)
+    pub fn into_owner(self) -> O
+    where
+        O: FromStorage,
+    {
+        let Self { value, storage } = self;
+        // The dependant must be dropped before the storage is converted back,
+        // as the owner may be moved out of it (eg. `AliasableBox`).
+        drop(value);
+        FromStorage::from_storage(storage)
     }
 
     /// Map the stored [`Dependant`] to another.
@@ -251,7 +464,7 @@ where
 
 impl<O, T> Zc<O, Option<T>>
 where
-    O: Owner,
+    O: IntoStorage,
 {
     /// Decomposes `self` into an option.
     #[inline]
@@ -262,7 +475,7 @@ where
 
 impl<O, Ok, Err> Zc<O, Result<Ok, Err>>
 where
-    O: Owner,
+    O: IntoStorage,
 {
     /// Decomposes `self` into a result.
     ///
@@ -277,7 +490,7 @@ where
 
 impl<O, T> From<Zc<O, Option<T>>> for Option<Zc<O, T>>
 where
-    O: Owner,
+    O: IntoStorage,
 {
     #[inline]
     fn from(zc: Zc<O, Option<T>>) -> Self {
@@ -293,7 +506,7 @@ where
 
 impl<O, Ok, Err> From<Zc<O, Result<Ok, Err>>> for Result<Zc<O, Ok>, Zc<O, Err>>
 where
-    O: Owner,
+    O: IntoStorage,
 {
     #[inline]
     fn from(zc: Zc<O, Result<Ok, Err>>) -> Self {
@@ -310,9 +523,32 @@ where
     }
 }
 
+// SAFETY: The dependant may only hold shared references to the storage's
+// target, so moving both between threads is safe if they are `Send`. A
+// dependant holding `&Target` is only `Send` if the target is `Sync`.
+unsafe impl<O, D> Send for Zc<O, D>
+where
+    O: IntoStorage,
+    O::Storage: Send,
+    D: Send,
+{
+}
+
+// SAFETY: Sharing `Zc` shares the dependant, the storage and the target (via
+// `as_owned`), so all must be `Sync`. The target is required explicitly as a
+// `Storage` being `Sync` does not imply its target is.
+unsafe impl<O, D> Sync for Zc<O, D>
+where
+    O: IntoStorage,
+    O::Storage: Sync,
+    <O::Storage as Deref>::Target: Sync,
+    D: Sync,
+{
+}
+
 impl<O, D> Display for Zc<O, D>
 where
-    O: Owner,
+    O: IntoStorage,
     D: Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -322,7 +558,7 @@ where
 
 impl<O, D> Debug for Zc<O, D>
 where
-    O: Owner,
+    O: IntoStorage,
     O::Storage: Debug,
     D: Debug,
 {
@@ -334,7 +570,21 @@ where
     }
 }
 
-/// Implemented for types that use data provided by an [`Owner`] and guarantee
+#[cfg(feature = "std")]
+impl<O, D> std::error::Error for Zc<O, D>
+where
+    O: IntoStorage,
+    O::Storage: Debug,
+    D: Dependant<'static> + std::error::Error,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // The source may borrow from the owner, so its concrete type is hidden
+        // to prevent it being downcast with a `'static` lifetime.
+        private::ErasedSource::<D, 1>::erase(&self.value)
+    }
+}
+
+/// Implemented for types that use data provided by an owner and guarantee
 /// that internal state is protected.
 ///
 /// # Derive implementations (recommended)
@@ -396,6 +646,8 @@ where
 /// }
 /// ```
 ///
+/// To be used with [`ZcMut`], [`Rebind`] must also be implemented.
+///
 /// # Safety
 ///
 /// Implementer must guarantee:
@@ -420,8 +672,27 @@ where
 ///
 /// See the [Rust Language Book] on interior mutability.
 ///
+/// For lazily computed fields, [`LazyField`] provides interior mutability
+/// that is a valid `Dependant`.
+///
+/// # Thread Safety
+///
+/// A [`Zc`] is `Send` if both the [`Storage`] and the dependant are `Send`,
+/// and is `Sync` if the [`Storage`], its target and the dependant are all
+/// `Sync`. As the dependant is stored with its lifetime erased these rules
+/// rely on the auto traits of the dependant reflecting what it borrows.
+///
+/// - A dependant holding `&'o T` is only `Send` if `T` is `Sync`, which is
+///   what prevents a `!Sync` target being shared across threads.
+/// - A dependant with a `!Send` field (eg. [`Rc<T>`]) makes the [`Zc`]
+///   `!Send`, even if the [`Storage`] is `Send`.
+///
+/// Manual implementations must not implement `Send` or `Sync` for a dependant
+/// that would otherwise be `!Send` or `!Sync` because of what it borrows.
+///
 /// [`Mutex<T>`]: std::sync::Mutex
 /// [`RefCell<T>`]: std::cell::RefCell
+/// [`Rc<T>`]: alloc::rc::Rc
 /// [Rust Language Book]: https://doc.rust-lang.org/book/ch15-05-interior-mutability.html
 pub unsafe trait Dependant<'o>: Sized + 'o {
     /// Always the exact same structure as `Self` but instead with a `'static`
@@ -429,23 +700,79 @@ pub unsafe trait Dependant<'o>: Sized + 'o {
     type Static: Dependant<'static>;
 }
 
+/// Implemented for the `'static` form of a [`Dependant`] to rebind it to the
+/// lifetime `'o`.
+///
+/// This is the inverse of [`Dependant::Static`] and is used to provide access
+/// to a stored dependant with a fresh lifetime that nothing outside of the
+/// owner can satisfy (see [`ZcMut::with_mut()`]).
+///
+/// The provided proc-macro implements this trait along with [`Dependant`].
+///
+/// # Manual implementations
+///
+/// ```
+/// struct MyStruct<'a>(&'a [u8]);
+///
+/// unsafe impl<'o> zc::Dependant<'o> for MyStruct<'o> {
+///     type Static = MyStruct<'static>;
+/// }
+///
+/// unsafe impl<'o> zc::Rebind<'o> for MyStruct<'static> {
+///     type Dependant = MyStruct<'o>;
+/// }
+/// ```
+///
+/// # Safety
+///
+/// Implementer must guarantee `Self::Dependant` is the exact same structure as
+/// `Self` but instead with the lifetime `'o`.
+pub unsafe trait Rebind<'o>: Dependant<'static> {
+    /// Always the exact same structure as `Self` but instead with the lifetime
+    /// `'o`.
+    type Dependant: Dependant<'o, Static = Self>;
+}
+
 /// Represents the owner of data with an associated storage type.
 ///
-/// An `Owner` is a convenience trait that can be implemented without the need
-/// of `unsafe` that returns a [`Storage`] that does require an `unsafe`
+/// `IntoStorage` is a convenience trait that can be implemented without the
+/// need of `unsafe` that returns a [`Storage`] that does require an `unsafe`
 /// implementation. See the notes on [`Storage`] to see why this it is required.
-pub trait Owner: Sized + 'static {
+///
+/// This is all that is required to construct a [`Zc`]. Owners that can be
+/// recovered from their storage should also implement [`FromStorage`].
+pub trait IntoStorage: Sized + 'static {
     /// The [`Storage`] type the owner uses.
     type Storage: Storage;
 
-    /// Consumes the `Owner` into the associated [`Storage`] type.
+    /// Consumes the owner into the associated [`Storage`] type.
     fn into_storage(self) -> Self::Storage;
+}
 
-    /// Consumes the associated [`Storage`] into the `Owner` type.
+/// Implemented for owners that can be recovered from their associated storage
+/// type.
+///
+/// Required for [`Zc::into_owner()`] and to return the owner on error from
+/// [`Zc::try_new()`]. Owners that cannot be turned back into their original
+/// type (eg. a `BytesMut` frozen into `Bytes`, or a foreign buffer with a drop
+/// callback) need only implement [`IntoStorage`].
+pub trait FromStorage: IntoStorage {
+    /// Consumes the associated [`Storage`] into the owner type.
     fn from_storage(storage: Self::Storage) -> Self;
 }
 
-impl<T> Owner for T
+/// Represents the owner of data that can be converted both into and from its
+/// associated storage type.
+///
+/// Automatically implemented for all types that implement both [`IntoStorage`]
+/// and [`FromStorage`], so cannot be implemented directly. Owners should
+/// instead implement [`IntoStorage`], and [`FromStorage`] if they can be
+/// recovered from their storage.
+pub trait Owner: IntoStorage + FromStorage {}
+
+impl<T> Owner for T where T: IntoStorage + FromStorage {}
+
+impl<T> IntoStorage for T
 where
     T: Storage,
 {
@@ -454,7 +781,12 @@ where
     fn into_storage(self) -> Self::Storage {
         self
     }
+}
 
+impl<T> FromStorage for T
+where
+    T: Storage,
+{
     fn from_storage(storage: Self::Storage) -> Self {
         storage
     }
@@ -488,3 +820,13 @@ where
 /// to will not change) but is not aliasable (see `noalias` above). Instead we
 /// can use the basic wrapper types provided by the [`aliasable`] crate.
 pub unsafe trait Storage: Sized + Deref + 'static {}
+
+/// Implemented for [`Storage`] types that can also safely provide a stable,
+/// aliasable mutable reference to data they own.
+///
+/// # Safety
+///
+/// In addition to the guarantees of [`Storage`], the implementer must
+/// guarantee that the reference provided via [`DerefMut`] points to the same
+/// data as [`Deref`] and that calling it does not move or invalidate the data.
+pub unsafe trait StorageMut: Storage + DerefMut {}
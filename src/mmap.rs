@@ -0,0 +1,120 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use memmap2::{Mmap, MmapOptions};
+
+use crate::private::Construct;
+use crate::{Dependant, Zc};
+
+impl<D> Zc<Mmap, D>
+where
+    D: Dependant<'static>,
+{
+    /// Construct a new zero-copied structure given the path of a file, mapped
+    /// into memory as the owner, and a function for constructing the
+    /// [`Dependant`].
+    ///
+    /// The file is never read into memory up front, so the dependant can index
+    /// into files much larger than memory, with pages read as they are
+    /// borrowed.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated, by this or any other
+    /// process, for as long as the `Zc` (or any [`Mmap`] recovered from it) is
+    /// alive. Doing so changes or unmaps the data the dependant borrows, which
+    /// is undefined behaviour. See the [safety notes of `memmap2`].
+    ///
+    /// A copy-on-write mapping (via [`Zc::map_file_copy()`]) does not avoid
+    /// this, as only pages written through the mapping are
+    /// copied, so modifications made to the file by other processes may still
+    /// be visible. Files that may be modified should be locked, or copied into
+    /// memory with [`Zc::from_reader()`] instead.
+    ///
+    /// # Errors
+    /// Returns an error if the file failed to be opened or mapped.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::Zc;
+    ///
+    /// fn entries(index: &[u8]) -> Vec<&[u8]> {
+    ///     index.split(|b| *b == b'\n').collect()
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("zc-map-file-example.idx");
+    /// std::fs::write(&path, "a\nb\nc").unwrap();
+    ///
+    /// // SAFETY: The file is not modified while mapped.
+    /// let index = unsafe { Zc::map_file(&path, entries) }.unwrap();
+    ///
+    /// assert_eq!(index.get::<Vec<&[u8]>>(), &[b"a", b"b", b"c"]);
+    /// # drop(index);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// [safety notes of `memmap2`]: memmap2::Mmap#safety
+    /// [`Zc::from_reader()`]: crate::Zc::from_reader()
+    pub unsafe fn map_file<P, C>(path: P, constructor: C) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        C: for<'o> Construct<'o, [u8], Dependant = D>,
+    {
+        Self::map_file_with(path, &MmapOptions::new(), constructor)
+    }
+
+    /// Construct a new zero-copied structure given the path of a file, mapped
+    /// into memory as the owner with the given options (eg. an offset and
+    /// length, or to populate the mapping up front), and a function for
+    /// constructing the [`Dependant`].
+    ///
+    /// The file is mapped shared and read-only. See [`Zc::map_file_copy()`]
+    /// for a copy-on-write mapping.
+    ///
+    /// # Safety
+    /// See [`Zc::map_file()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file failed to be opened or mapped.
+    pub unsafe fn map_file_with<P, C>(
+        path: P,
+        options: &MmapOptions,
+        constructor: C,
+    ) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        C: for<'o> Construct<'o, [u8], Dependant = D>,
+    {
+        let file = File::open(path)?;
+        // SAFETY: The file is not modified while mapped, as upheld by the
+        // caller.
+        let mmap = options.map(&file)?;
+        Ok(Self::new(mmap, constructor))
+    }
+
+    /// Construct a new zero-copied structure given the path of a file, mapped
+    /// into memory as a private copy-on-write mapping with the given options
+    /// (via [`MmapOptions::map_copy_read_only()`]), and a function for
+    /// constructing the [`Dependant`].
+    ///
+    /// # Safety
+    /// See [`Zc::map_file()`].
+    ///
+    /// # Errors
+    /// Returns an error if the file failed to be opened or mapped.
+    pub unsafe fn map_file_copy<P, C>(
+        path: P,
+        options: &MmapOptions,
+        constructor: C,
+    ) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+        C: for<'o> Construct<'o, [u8], Dependant = D>,
+    {
+        let file = File::open(path)?;
+        // SAFETY: The file is not modified while mapped, as upheld by the
+        // caller.
+        let mmap = options.map_copy_read_only(&file)?;
+        Ok(Self::new(mmap, constructor))
+    }
+}
@@ -0,0 +1,210 @@
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use alloc::boxed::Box;
+
+use aliasable::boxed::AliasableBox;
+
+use crate::Storage;
+
+/// [`Storage`] adapter for any type that provides a reference to its data via
+/// [`AsRef`].
+///
+/// The value is boxed into an [`AliasableBox`] so the reference it provides
+/// remains stable when moved. `U` is the target type, defaulting to `[u8]`.
+///
+/// # Example
+/// ```
+/// use zc::{BoxedOwner, Zc};
+///
+/// fn tail(s: &str) -> &str {
+///     &s[1..]
+/// }
+///
+/// let owner = BoxedOwner::<_, str>::new(String::from("hello"));
+/// let data = Zc::new(owner, tail);
+///
+/// assert_eq!(data.get::<&str>(), &"ello");
+/// assert_eq!(data.into_owner().into_inner(), "hello");
+/// ```
+pub struct BoxedOwner<T, U: ?Sized = [u8]> {
+    inner: AliasableBox<T>,
+    target: PhantomData<fn() -> *const U>,
+}
+
+impl<T, U> BoxedOwner<T, U>
+where
+    T: AsRef<U> + 'static,
+    U: ?Sized + 'static,
+{
+    /// Construct a new `BoxedOwner` given a value that provides the target.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: AliasableBox::from_unique(Box::new(inner)),
+            target: PhantomData,
+        }
+    }
+
+    /// Consumes `self` into the wrapped value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        *AliasableBox::into_unique(self.inner)
+    }
+}
+
+impl<T, U> Deref for BoxedOwner<T, U>
+where
+    T: AsRef<U> + 'static,
+    U: ?Sized + 'static,
+{
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        (*self.inner).as_ref()
+    }
+}
+
+impl<T, U> Debug for BoxedOwner<T, U>
+where
+    T: Debug,
+    U: ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoxedOwner").field(&*self.inner).finish()
+    }
+}
+
+// SAFETY: The value is boxed and never mutably borrowed, so any reference it
+// provides via `AsRef` from a shared borrow stays valid until it is dropped.
+unsafe impl<T, U> Storage for BoxedOwner<T, U>
+where
+    T: AsRef<U> + 'static,
+    U: ?Sized + 'static,
+{
+}
+
+/// Type-erased [`Storage`] for any type that provides a reference to its data
+/// via [`AsRef`].
+///
+/// Allows exposing a `Zc<DynOwner, D>` without leaking where the data came
+/// from (eg. a `Vec<u8>`, `String` or network buffer). `U` is the target
+/// type, defaulting to `[u8]`.
+///
+/// `B` is the erased type, defaulting to `dyn AsRef<U> + Send + Sync` so the
+/// `Zc` can be sent between threads. Owners that are not thread-safe (eg.
+/// behind an `Rc`) can instead be erased into `dyn AsRef<U>` with
+/// [`DynOwner::new_local()`].
+///
+/// # Example
+/// ```
+/// use zc::{DynOwner, Zc};
+///
+/// fn tail(bytes: &[u8]) -> &[u8] {
+///     &bytes[1..]
+/// }
+///
+/// fn parse(owner: DynOwner) -> Zc<DynOwner, &'static [u8]> {
+///     Zc::new(owner, tail)
+/// }
+///
+/// let from_vec = parse(DynOwner::new(vec![1, 2, 3]));
+/// let from_str = parse(DynOwner::new("abc"));
+///
+/// assert_eq!(from_vec.get::<&[u8]>(), &[2, 3]);
+/// assert_eq!(from_str.get::<&[u8]>(), b"bc");
+/// ```
+pub struct DynOwner<U: ?Sized = [u8], B: ?Sized = dyn AsRef<U> + Send + Sync> {
+    inner: AliasableBox<B>,
+    target: PhantomData<fn() -> *const U>,
+}
+
+impl<U> DynOwner<U>
+where
+    U: ?Sized + 'static,
+{
+    /// Construct a new `DynOwner` given a value that provides the target.
+    pub fn new<T>(inner: T) -> Self
+    where
+        T: AsRef<U> + Send + Sync + 'static,
+    {
+        let inner: Box<dyn AsRef<U> + Send + Sync> = Box::new(inner);
+        Self::from_box(inner)
+    }
+}
+
+impl<U> DynOwner<U, dyn AsRef<U>>
+where
+    U: ?Sized + 'static,
+{
+    /// Construct a new `DynOwner` given a value that provides the target,
+    /// without requiring it to be thread-safe.
+    ///
+    /// # Example
+    /// ```
+    /// use std::rc::Rc;
+    /// use zc::{DynOwner, Zc};
+    ///
+    /// fn tail(bytes: &[u8]) -> &[u8] {
+    ///     &bytes[1..]
+    /// }
+    ///
+    /// let shared: Rc<[u8]> = Rc::from(&[1, 2, 3][..]);
+    /// let data = Zc::new(DynOwner::new_local(shared), tail);
+    ///
+    /// assert_eq!(data.get::<&[u8]>(), &[2, 3]);
+    /// ```
+    pub fn new_local<T>(inner: T) -> Self
+    where
+        T: AsRef<U> + 'static,
+    {
+        let inner: Box<dyn AsRef<U>> = Box::new(inner);
+        Self::from_box(inner)
+    }
+}
+
+impl<U, B> DynOwner<U, B>
+where
+    U: ?Sized + 'static,
+    B: ?Sized + AsRef<U> + 'static,
+{
+    /// Construct a new `DynOwner` given an already erased value that provides
+    /// the target.
+    #[must_use]
+    pub fn from_box(inner: Box<B>) -> Self {
+        Self {
+            inner: AliasableBox::from_unique(inner),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<U, B> Deref for DynOwner<U, B>
+where
+    U: ?Sized + 'static,
+    B: ?Sized + AsRef<U> + 'static,
+{
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        (*self.inner).as_ref()
+    }
+}
+
+impl<U, B> Debug for DynOwner<U, B>
+where
+    U: ?Sized + Debug + 'static,
+    B: ?Sized + AsRef<U> + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DynOwner").field(&&**self).finish()
+    }
+}
+
+// SAFETY: See `BoxedOwner`.
+unsafe impl<U, B> Storage for DynOwner<U, B>
+where
+    U: ?Sized + 'static,
+    B: ?Sized + AsRef<U> + 'static,
+{
+}
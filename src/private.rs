@@ -1,14 +1,52 @@
+use core::future::Future;
+use core::ops::Range;
 use core::{mem, ptr};
 
-use crate::Dependant;
+use crate::{Dependant, Rebind};
 
-unsafe fn erase_lifetime<'o, D: Dependant<'o>>(dependant: D) -> D::Static {
-    let self_ptr: *const D = &dependant;
+/// Erases the lifetime of a [`Dependant`].
+///
+/// # Safety
+///
+/// The returned dependant must not outlive the data it references.
+pub unsafe fn erase_lifetime<'o, D: Dependant<'o>>(dependant: D) -> D::Static {
+    let self_ptr: *const D = ptr::addr_of!(dependant);
     let erased = ptr::read(self_ptr.cast::<D::Static>());
     mem::forget(dependant);
     erased
 }
 
+/// Rebinds a [`Dependant`] with an erased lifetime to the lifetime `'a`.
+///
+/// # Safety
+///
+/// The data the dependant references must be valid for `'a`.
+pub unsafe fn rebind<'a, D: Rebind<'a>>(dependant: D) -> D::Dependant {
+    let self_ptr: *const D = ptr::addr_of!(dependant);
+    let rebound = ptr::read(self_ptr.cast::<D::Dependant>());
+    mem::forget(dependant);
+    rebound
+}
+
+/// Returns the byte range of `sub` within `outer`, comparing addresses.
+pub fn span_within(outer: &[u8], sub: &[u8]) -> Option<Range<usize>> {
+    let outer_start = outer.as_ptr() as usize;
+    let sub_start = sub.as_ptr() as usize;
+    let start = sub_start.checked_sub(outer_start)?;
+    let end = start.checked_add(sub.len())?;
+    if end <= outer.len() {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+/// Constructs a [`Dependant`] with its lifetime erased given a reference to
+/// the owned data.
+///
+/// # Safety
+///
+/// The returned dependant must not outlive the data it was constructed from.
 pub unsafe trait Construct<'o, O>: Sized
 where
     O: ?Sized,
@@ -31,6 +69,41 @@ where
     }
 }
 
+/// Asynchronously constructs a [`Dependant`] given a reference to the owned
+/// data, with the future borrowing the owned data for `'o`.
+pub trait ConstructAsync<'o, O>: Sized
+where
+    O: ?Sized,
+{
+    type Dependant: Dependant<'static>;
+    type Output: Dependant<'o, Static = Self::Dependant>;
+    type Future: Future<Output = Self::Output> + 'o;
+
+    fn construct_async(self, owned: &'o O) -> Self::Future;
+}
+
+impl<'o, O, D, F, Fut> ConstructAsync<'o, O> for F
+where
+    O: ?Sized + 'o,
+    D: Dependant<'o>,
+    F: FnOnce(&'o O) -> Fut + 'static,
+    Fut: Future<Output = D> + 'o,
+{
+    type Dependant = D::Static;
+    type Output = D;
+    type Future = Fut;
+
+    fn construct_async(self, owned: &'o O) -> Self::Future {
+        (self)(owned)
+    }
+}
+
+/// Try constructs a [`Dependant`] with its lifetime erased given a reference
+/// to the owned data.
+///
+/// # Safety
+///
+/// The returned dependant must not outlive the data it was constructed from.
 pub unsafe trait TryConstruct<'o, O>: Sized
 where
     O: ?Sized,
@@ -55,3 +128,152 @@ where
         (self)(owned).map(|d| erase_lifetime(d))
     }
 }
+
+/// Constructs a [`Dependant`] with its lifetime erased given a mutable
+/// reference to the owned data.
+///
+/// # Safety
+///
+/// The returned dependant must not outlive the data it was constructed from.
+pub unsafe trait ConstructMut<'o, O>: Sized
+where
+    O: ?Sized,
+{
+    type Dependant: Dependant<'static>;
+
+    unsafe fn construct_mut(self, owned: &'o mut O) -> Self::Dependant;
+}
+
+unsafe impl<'o, O, D, F> ConstructMut<'o, O> for F
+where
+    O: ?Sized + 'o,
+    D: Dependant<'o>,
+    F: FnOnce(&'o mut O) -> D + 'static,
+{
+    type Dependant = D::Static;
+
+    unsafe fn construct_mut(self, owned: &'o mut O) -> Self::Dependant {
+        erase_lifetime((self)(owned))
+    }
+}
+
+/// Try constructs a [`Dependant`] with its lifetime erased given a mutable
+/// reference to the owned data.
+///
+/// # Safety
+///
+/// The returned dependant must not outlive the data it was constructed from.
+pub unsafe trait TryConstructMut<'o, O>: Sized
+where
+    O: ?Sized,
+{
+    type Error: 'static;
+    type Dependant: Dependant<'static>;
+
+    unsafe fn try_construct_mut(self, owned: &'o mut O) -> Result<Self::Dependant, Self::Error>;
+}
+
+unsafe impl<'o, O, D, E, F> TryConstructMut<'o, O> for F
+where
+    E: 'static,
+    O: ?Sized + 'o,
+    D: Dependant<'o>,
+    F: FnOnce(&'o mut O) -> Result<D, E> + 'static,
+{
+    type Error = E;
+    type Dependant = D::Static;
+
+    unsafe fn try_construct_mut(self, owned: &'o mut O) -> Result<Self::Dependant, Self::Error> {
+        (self)(owned).map(|d| erase_lifetime(d))
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::error::ErasedSource;
+
+#[cfg(feature = "std")]
+mod error {
+    use core::fmt;
+    use std::error::Error;
+
+    /// Wrapper that hides the concrete type of the `N`th source of an error
+    /// that may have had its lifetime erased.
+    ///
+    /// The source can only be reached via `Display`, `Debug` and `source()`,
+    /// which prevents it from being downcast into a type with a `'static`
+    /// lifetime. The wrapper is over the root error (and not the source itself)
+    /// so it remains sized and can be returned as a trait object.
+    #[repr(transparent)]
+    pub struct ErasedSource<E, const N: usize>(E);
+
+    impl<E, const N: usize> ErasedSource<E, N>
+    where
+        E: Error + 'static,
+        Self: Error,
+    {
+        /// Returns the erased `N`th source of `err` if it exists.
+        pub fn erase(err: &E) -> Option<&(dyn Error + 'static)> {
+            let err_ptr: *const E = err;
+            // SAFETY: `ErasedSource` is `repr(transparent)` over the error.
+            let erased: &Self = unsafe { &*err_ptr.cast::<Self>() };
+            let source: &(dyn Error + 'static) = erased;
+            erased.get().map(|_| source)
+        }
+    }
+
+    impl<E, const N: usize> ErasedSource<E, N>
+    where
+        E: Error + 'static,
+    {
+        fn get(&self) -> Option<&(dyn Error + 'static)> {
+            let mut source = self.0.source();
+            for _ in 1..N {
+                source = source?.source();
+            }
+            source
+        }
+
+        fn source_unchecked(&self) -> &(dyn Error + 'static) {
+            self.get().expect("erased source exists")
+        }
+    }
+
+    impl<E, const N: usize> fmt::Debug for ErasedSource<E, N>
+    where
+        E: Error + 'static,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Debug::fmt(self.source_unchecked(), f)
+        }
+    }
+
+    impl<E, const N: usize> fmt::Display for ErasedSource<E, N>
+    where
+        E: Error + 'static,
+    {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(self.source_unchecked(), f)
+        }
+    }
+
+    macro_rules! impl_erased_source {
+        ($($n:literal => $next:literal)*) => {
+            $(impl<E> Error for ErasedSource<E, $n>
+            where
+                E: Error + 'static,
+            {
+                fn source(&self) -> Option<&(dyn Error + 'static)> {
+                    ErasedSource::<E, $next>::erase(&self.0)
+                }
+            })*
+        };
+    }
+
+    impl_erased_source!(
+        1 => 2 2 => 3 3 => 4 4 => 5 5 => 6 6 => 7 7 => 8
+        8 => 9 9 => 10 10 => 11 11 => 12 12 => 13 13 => 14 14 => 15 15 => 16
+    );
+
+    // Source chains are truncated beyond this depth.
+    impl<E> Error for ErasedSource<E, 16> where E: Error + 'static {}
+}
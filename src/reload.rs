@@ -0,0 +1,222 @@
+use core::fmt::{self, Debug, Display};
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use alloc::boxed::Box;
+
+use arc_swap::ArcSwap;
+
+use crate::private::TryConstruct;
+use crate::{Dependant, FromStorage, IntoStorage, Target, Zc};
+
+type BuildFn<O, D, E> =
+    Box<dyn Fn(O) -> Result<Zc<O, D>, (E, <O as IntoStorage>::Storage)> + Send + Sync>;
+
+/// Hot-reloadable [`Zc`] that is atomically swapped when rebuilt from a new
+/// owner.
+///
+/// Readers [`load()`] the current `Arc<Zc<O, D>>` without locking, and keep a
+/// consistent snapshot for as long as they hold it, even across reloads. If
+/// the [`Dependant`] fails to be constructed from a new owner, the current
+/// version is kept.
+///
+/// Owners that cannot be recovered from their storage (such as a memory map)
+/// are reloaded via [`ZcReloader::reload_or_drop()`], which drops an owner the
+/// dependant fails to be constructed from. Owners implementing
+/// [`FromStorage`] can instead be reloaded via [`ZcReloader::reload()`],
+/// which returns it.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, Zc, ZcReloader};
+///
+/// #[derive(Dependant)]
+/// pub struct Config<'a> {
+///     name: &'a str,
+/// }
+///
+/// fn parse(source: &str) -> Result<Config<'_>, &'static str> {
+///     let name = source.strip_prefix("name=").ok_or("expected name")?;
+///     Ok(Config { name })
+/// }
+///
+/// let reloader = ZcReloader::new(String::from("name=a"), parse).unwrap();
+/// let before = reloader.load();
+///
+/// assert!(reloader.reload(String::from("invalid")).is_err());
+/// assert_eq!(reloader.load().get::<Config>().name, "a");
+///
+/// reloader.reload(String::from("name=b")).unwrap();
+/// assert_eq!(reloader.load().get::<Config>().name, "b");
+/// assert_eq!(before.get::<Config>().name, "a");
+/// ```
+///
+/// [`load()`]: ZcReloader::load()
+pub struct ZcReloader<O, D, E>
+where
+    O: IntoStorage,
+{
+    current: ArcSwap<Zc<O, D>>,
+    build: BuildFn<O, D, E>,
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl<O, D, E> ZcReloader<O, D, E>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+    E: 'static,
+{
+    /// Construct a new `ZcReloader` given an initial owner and a function for
+    /// constructing the [`Dependant`] from each owner.
+    ///
+    /// # Errors
+    /// Returns `E` if the constructor failed.
+    pub fn new<C>(owner: O, constructor: C) -> Result<Self, E>
+    where
+        C: for<'o> TryConstruct<'o, Target<O>, Error = E, Dependant = D>,
+        C: Clone + Send + Sync + 'static,
+    {
+        let build = move |owner| Zc::try_new_in_storage(owner, constructor.clone());
+        let zc = build(owner).map_err(|(err, _)| err)?;
+        Ok(Self {
+            current: ArcSwap::from_pointee(zc),
+            build: Box::new(build),
+            modified: Mutex::new(None),
+        })
+    }
+
+    /// Returns the current version.
+    pub fn load(&self) -> Arc<Zc<O, D>> {
+        self.current.load_full()
+    }
+
+    /// Rebuilds from a new owner, returning the new version.
+    ///
+    /// # Errors
+    /// Returns `E` if the constructor failed, in which case the owner is
+    /// dropped and the current version is kept.
+    pub fn reload_or_drop(&self, owner: O) -> Result<Arc<Zc<O, D>>, E> {
+        let zc = (self.build)(owner).map_err(|(err, _)| err)?;
+        Ok(self.store(zc))
+    }
+
+    /// Rebuilds from a file read with the given function (such as
+    /// [`std::fs::read_to_string()`]), returning the new version.
+    ///
+    /// # Errors
+    /// Returns an error if the file failed to be read or the constructor
+    /// failed, in which case the current version is kept.
+    pub fn reload_file<P, R>(&self, path: P, read: R) -> Result<Arc<Zc<O, D>>, ReloadError<E>>
+    where
+        P: AsRef<Path>,
+        R: FnOnce(P) -> io::Result<O>,
+    {
+        let mut modified = self.lock_modified();
+        let file_modified = path.as_ref().metadata()?.modified()?;
+        let owner = read(path)?;
+        // A file that fails to load is not retried by `poll_file` until it is
+        // modified again.
+        *modified = Some(file_modified);
+        self.reload_or_drop(owner).map_err(ReloadError::Construct)
+    }
+
+    /// Rebuilds from a file read with the given function if it has been
+    /// modified since last loaded via [`ZcReloader::reload_file()`] or
+    /// [`ZcReloader::poll_file()`], returning `true` if reloaded.
+    ///
+    /// # Errors
+    /// Returns an error if the file failed to be read or the constructor
+    /// failed, in which case the current version is kept.
+    pub fn poll_file<P, R>(&self, path: P, read: R) -> Result<bool, ReloadError<E>>
+    where
+        P: AsRef<Path>,
+        R: FnOnce(P) -> io::Result<O>,
+    {
+        let file_modified = path.as_ref().metadata()?.modified()?;
+        if *self.lock_modified() == Some(file_modified) {
+            return Ok(false);
+        }
+        self.reload_file(path, read).map(|_| true)
+    }
+
+    fn store(&self, zc: Zc<O, D>) -> Arc<Zc<O, D>> {
+        let zc = Arc::new(zc);
+        self.current.store(Arc::clone(&zc));
+        zc
+    }
+
+    fn lock_modified(&self) -> MutexGuard<'_, Option<SystemTime>> {
+        self.modified
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl<O, D, E> ZcReloader<O, D, E>
+where
+    O: FromStorage,
+    D: Dependant<'static>,
+    E: 'static,
+{
+    /// Rebuilds from a new owner, returning the new version.
+    ///
+    /// # Errors
+    /// Returns `E` along with the owner if the constructor failed, in which
+    /// case the current version is kept.
+    pub fn reload(&self, owner: O) -> Result<Arc<Zc<O, D>>, (E, O)> {
+        let zc = (self.build)(owner).map_err(|(err, storage)| (err, O::from_storage(storage)))?;
+        Ok(self.store(zc))
+    }
+}
+
+impl<O, D, E> Debug for ZcReloader<O, D, E>
+where
+    O: IntoStorage,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZcReloader")
+            .field("modified", &self.modified)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error returned from reloading a [`ZcReloader`] from a file.
+#[derive(Debug)]
+pub enum ReloadError<E> {
+    /// The file failed to be read.
+    Io(io::Error),
+    /// The [`Dependant`] failed to be constructed.
+    ///
+    /// [`Dependant`]: crate::Dependant
+    Construct(E),
+}
+
+impl<E> From<io::Error> for ReloadError<E> {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl<E: Display> Display for ReloadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read file: {err}"),
+            Self::Construct(err) => write!(f, "failed to construct dependant: {err}"),
+        }
+    }
+}
+
+impl<E> std::error::Error for ReloadError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Construct(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Bound, Deref, RangeBounds};
+use core::str::{self, Utf8Error};
+
+use alloc::sync::Arc;
+
+use crate::Zc;
+
+/// Cheaply cloneable and sliceable handle to bytes borrowed from a
+/// reference-counted owner.
+///
+/// A `ZcBytes` is a [`Zc`] of an [`Arc`] owner and a slice borrowed from it.
+/// Cloning and slicing only clone the `Arc`, and the owner can be anything the
+/// `Arc` can hold, not only byte buffers. Any `Zc<Arc<O>, &[u8]>` can be
+/// converted into a `ZcBytes` without copying.
+///
+/// # Example
+/// ```
+/// use zc::ZcBytes;
+///
+/// let mut bytes = ZcBytes::new(b"hello world".to_vec());
+/// let hello = bytes.split_to(5);
+///
+/// assert_eq!(hello, b"hello"[..]);
+/// assert_eq!(bytes.slice(1..), b"world"[..]);
+/// assert_eq!(bytes.owner(), b"hello world");
+/// ```
+pub struct ZcBytes<O = [u8]>
+where
+    O: ?Sized + 'static,
+{
+    zc: Zc<Arc<O>, &'static [u8]>,
+}
+
+impl<O> ZcBytes<O>
+where
+    O: ?Sized + AsRef<[u8]> + 'static,
+{
+    /// Construct a new `ZcBytes` referencing all of the bytes of the owner.
+    pub fn new(owner: O) -> Self
+    where
+        O: Sized,
+    {
+        Self::from_arc(Arc::new(owner))
+    }
+
+    /// Construct a new `ZcBytes` referencing all of the bytes of a shared
+    /// owner.
+    pub fn from_arc(owner: Arc<O>) -> Self {
+        fn as_bytes<O: ?Sized + AsRef<[u8]>>(owner: &O) -> &[u8] {
+            owner.as_ref()
+        }
+        Self {
+            zc: Zc::new(owner, as_bytes::<O>),
+        }
+    }
+}
+
+impl<O> ZcBytes<O>
+where
+    O: ?Sized + 'static,
+{
+    /// Returns a reference to the owner.
+    #[must_use]
+    pub fn owner(&self) -> &O {
+        self.zc.as_owned()
+    }
+
+    /// Returns the bytes referenced.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.zc.value
+    }
+
+    /// Returns a new handle to a sub-slice of the bytes.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    #[must_use]
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let bytes = self.zc.value;
+        let (start, end) = bounds(&range, bytes.len());
+        // SAFETY: The sub-slice is borrowed from the same owner.
+        unsafe { self.with_value(&bytes[start..end]) }
+    }
+
+    /// Splits the bytes in two at the index, returning `[0, at)` and leaving
+    /// `self` with `[at, len)`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`.
+    #[must_use = "consider using `ZcBytes::slice()` if the split off bytes are not needed"]
+    pub fn split_to(&mut self, at: usize) -> Self {
+        let (front, back) = self.zc.value.split_at(at);
+        // SAFETY: Both sides are borrowed from the same owner.
+        let front = unsafe { self.with_value(front) };
+        self.zc.value = back;
+        front
+    }
+
+    /// Splits the bytes in two at the index, returning `[at, len)` and leaving
+    /// `self` with `[0, at)`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`.
+    #[must_use = "consider using `ZcBytes::slice()` if the split off bytes are not needed"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let (front, back) = self.zc.value.split_at(at);
+        // SAFETY: Both sides are borrowed from the same owner.
+        let back = unsafe { self.with_value(back) };
+        self.zc.value = front;
+        back
+    }
+
+    /// Consumes `self` into the underlying [`Zc`].
+    #[must_use]
+    pub fn into_zc(self) -> Zc<Arc<O>, &'static [u8]> {
+        self.zc
+    }
+
+    /// # Safety
+    /// The value must be borrowed from the owner of `self`.
+    unsafe fn with_value(&self, value: &'static [u8]) -> Self {
+        Self {
+            zc: Zc {
+                value,
+                storage: Arc::clone(&self.zc.storage),
+            },
+        }
+    }
+}
+
+impl<O> From<Zc<Arc<O>, &'static [u8]>> for ZcBytes<O>
+where
+    O: ?Sized + 'static,
+{
+    fn from(zc: Zc<Arc<O>, &'static [u8]>) -> Self {
+        Self { zc }
+    }
+}
+
+impl<O> From<ZcStr<O>> for ZcBytes<O>
+where
+    O: ?Sized + 'static,
+{
+    fn from(s: ZcStr<O>) -> Self {
+        s.bytes
+    }
+}
+
+/// Cheaply cloneable and sliceable handle to a string borrowed from a
+/// reference-counted owner.
+///
+/// See [`ZcBytes`], which this wraps with the guarantee that the bytes are
+/// valid UTF-8.
+///
+/// # Example
+/// ```
+/// use zc::ZcStr;
+///
+/// let s = ZcStr::new(String::from("key=value"));
+/// let (key, value) = s.split_once('=').unwrap();
+///
+/// assert_eq!(key, "key");
+/// assert_eq!(value.to_string(), "value");
+/// ```
+pub struct ZcStr<O = str>
+where
+    O: ?Sized + 'static,
+{
+    bytes: ZcBytes<O>,
+}
+
+impl<O> ZcStr<O>
+where
+    O: ?Sized + AsRef<str> + 'static,
+{
+    /// Construct a new `ZcStr` referencing all of the string of the owner.
+    pub fn new(owner: O) -> Self
+    where
+        O: Sized,
+    {
+        Self::from_arc(Arc::new(owner))
+    }
+
+    /// Construct a new `ZcStr` referencing all of the string of a shared
+    /// owner.
+    pub fn from_arc(owner: Arc<O>) -> Self {
+        fn as_bytes<O: ?Sized + AsRef<str>>(owner: &O) -> &[u8] {
+            owner.as_ref().as_bytes()
+        }
+        Self {
+            bytes: ZcBytes {
+                zc: Zc::new(owner, as_bytes::<O>),
+            },
+        }
+    }
+}
+
+impl<O> ZcStr<O>
+where
+    O: ?Sized + 'static,
+{
+    /// Converts bytes into a `ZcStr` if they are valid UTF-8.
+    ///
+    /// # Errors
+    /// Returns the error along with the bytes if they are not valid UTF-8.
+    pub fn from_utf8(bytes: ZcBytes<O>) -> Result<Self, (Utf8Error, ZcBytes<O>)> {
+        match str::from_utf8(bytes.as_bytes()) {
+            Ok(_) => Ok(Self { bytes }),
+            Err(err) => Err((err, bytes)),
+        }
+    }
+
+    /// Returns a reference to the owner.
+    #[must_use]
+    pub fn owner(&self) -> &O {
+        self.bytes.owner()
+    }
+
+    /// Returns the string referenced.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        self.erased_str()
+    }
+
+    /// Returns a new handle to a sub-slice of the string.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds or not on character boundaries.
+    #[must_use]
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let s = self.erased_str();
+        let (start, end) = bounds(&range, s.len());
+        // SAFETY: The sub-slice is borrowed from the same owner.
+        unsafe { self.with_str(&s[start..end]) }
+    }
+
+    /// Splits the string in two at the index, returning `[0, at)` and leaving
+    /// `self` with `[at, len)`.
+    ///
+    /// # Panics
+    /// Panics if `at > len` or is not on a character boundary.
+    #[must_use = "consider using `ZcStr::slice()` if the split off string is not needed"]
+    pub fn split_to(&mut self, at: usize) -> Self {
+        assert!(self.as_str().is_char_boundary(at), "not a char boundary");
+        Self {
+            bytes: self.bytes.split_to(at),
+        }
+    }
+
+    /// Splits the string in two at the index, returning `[at, len)` and leaving
+    /// `self` with `[0, at)`.
+    ///
+    /// # Panics
+    /// Panics if `at > len` or is not on a character boundary.
+    #[must_use = "consider using `ZcStr::slice()` if the split off string is not needed"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(self.as_str().is_char_boundary(at), "not a char boundary");
+        Self {
+            bytes: self.bytes.split_off(at),
+        }
+    }
+
+    /// Splits the string on the first occurrence of the delimiter, returning
+    /// the parts before and after it.
+    #[must_use]
+    pub fn split_once(&self, delimiter: char) -> Option<(Self, Self)> {
+        let s = self.erased_str();
+        let index = s.find(delimiter)?;
+        let (before, after) = (&s[..index], &s[index + delimiter.len_utf8()..]);
+        // SAFETY: Both parts are borrowed from the same owner.
+        unsafe { Some((self.with_str(before), self.with_str(after))) }
+    }
+
+    /// Consumes `self` into the underlying [`Zc`] of the bytes.
+    #[must_use]
+    pub fn into_zc(self) -> Zc<Arc<O>, &'static [u8]> {
+        self.bytes.into_zc()
+    }
+
+    fn erased_str(&self) -> &'static str {
+        // SAFETY: The bytes are checked to be valid UTF-8 on construction, and
+        // only ever split on character boundaries.
+        unsafe { str::from_utf8_unchecked(self.bytes.zc.value) }
+    }
+
+    /// # Safety
+    /// The string must be borrowed from the owner of `self`.
+    unsafe fn with_str(&self, s: &'static str) -> Self {
+        Self {
+            bytes: self.bytes.with_value(s.as_bytes()),
+        }
+    }
+}
+
+fn bounds<R: RangeBounds<usize>>(range: &R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// Common impls
+
+macro_rules! impl_shared {
+    ($ty:ident, $target:ty, $as_target:ident) => {
+        impl<O> Clone for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn clone(&self) -> Self {
+                self.slice(..)
+            }
+        }
+
+        impl<O> Deref for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            type Target = $target;
+
+            fn deref(&self) -> &$target {
+                self.$as_target()
+            }
+        }
+
+        impl<O> AsRef<$target> for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn as_ref(&self) -> &$target {
+                self.$as_target()
+            }
+        }
+
+        impl<O> Borrow<$target> for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn borrow(&self) -> &$target {
+                self.$as_target()
+            }
+        }
+
+        impl<O> PartialEq for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn eq(&self, other: &Self) -> bool {
+                self.$as_target() == other.$as_target()
+            }
+        }
+
+        impl<O> Eq for $ty<O> where O: ?Sized + 'static {}
+
+        impl<O> PartialEq<$target> for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn eq(&self, other: &$target) -> bool {
+                self.$as_target() == other
+            }
+        }
+
+        impl<O> PartialEq<&$target> for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn eq(&self, other: &&$target) -> bool {
+                self.$as_target() == *other
+            }
+        }
+
+        impl<O> PartialOrd for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<O> Ord for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.$as_target().cmp(other.$as_target())
+            }
+        }
+
+        impl<O> Hash for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.$as_target().hash(state);
+            }
+        }
+
+        impl<O> Debug for $ty<O>
+        where
+            O: ?Sized + 'static,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Debug::fmt(self.$as_target(), f)
+            }
+        }
+    };
+}
+
+impl_shared!(ZcBytes, [u8], as_bytes);
+impl_shared!(ZcStr, str, as_str);
+
+impl<O> Display for ZcStr<O>
+where
+    O: ?Sized + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
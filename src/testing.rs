@@ -0,0 +1,249 @@
+//! Helpers for testing custom [`Storage`], [`IntoStorage`] and [`Dependant`]
+//! implementations.
+//!
+//! Implementing these traits by hand is `unsafe`, as `Zc` relies on the
+//! guarantees they make. The helpers here check those guarantees hold for a
+//! given value, panicking with a description of the first one broken. They are
+//! intended to be called from the tests of crates adding their own owners or
+//! dependants, and are small enough to run under [Miri].
+//!
+//! # Example
+//! ```
+//! use zc::testing;
+//! use zc::Dependant;
+//!
+//! #[derive(Debug, Dependant)]
+//! pub struct Fields<'a>(Vec<&'a [u8]>);
+//!
+//! fn parse(bytes: &[u8]) -> Fields<'_> {
+//!     Fields(bytes.split(|b| *b == b',').collect())
+//! }
+//!
+//! testing::assert_storage_stable(b"a,b".to_vec());
+//! testing::assert_dependant_layout::<Fields>();
+//! testing::stress(|| b"a,b".to_vec(), parse, 8);
+//!
+//! zc::assert_covariant!(Fields);
+//! ```
+//!
+//! [`Storage`]: crate::Storage
+//! [`IntoStorage`]: crate::IntoStorage
+//! [`Dependant`]: crate::Dependant
+//! [Miri]: https://github.com/rust-lang/miri
+
+use core::any::type_name;
+use core::fmt::{self, Debug, Write};
+use core::mem::{align_of, size_of, swap};
+use core::ops::{Deref, Range};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::private::Construct;
+use crate::verify::address_range;
+use crate::{Dependant, Extents, FromStorage, IntoStorage, References, Storage, Target, Zc};
+
+/// Asserts that the target of the storage of an owner does not move when the
+/// storage is moved, or when it is round-tripped through the owner.
+///
+/// Owners that do not implement [`FromStorage`], or that store themselves in
+/// a box (and so are reboxed on each round-trip), should instead use
+/// [`assert_moves_stable()`].
+///
+/// # Panics
+/// Panics if the target moves.
+///
+/// [`FromStorage`]: crate::FromStorage
+pub fn assert_storage_stable<O>(owner: O)
+where
+    O: FromStorage,
+{
+    let storage = assert_moves_stable(owner);
+    let expected = address_range(&*storage);
+    let storage = O::from_storage(storage).into_storage();
+    assert_target(&storage, &expected, "round-tripping through the owner");
+}
+
+/// Asserts that the target of the storage of an owner does not move when the
+/// storage is moved, returning the storage.
+///
+/// # Panics
+/// Panics if the target moves.
+pub fn assert_moves_stable<O>(owner: O) -> O::Storage
+where
+    O: IntoStorage,
+{
+    let storage = owner.into_storage();
+    let expected = address_range(&*storage);
+    assert_target(&storage, &expected, "dereferencing again");
+
+    let storage = *Box::new(storage);
+    assert_target(&storage, &expected, "moving into and out of a box");
+
+    let mut storages = Vec::with_capacity(1);
+    storages.push(storage);
+    storages.reserve(64);
+    let storage = storages.pop().unwrap();
+    assert_target(&storage, &expected, "reallocating a vec containing it");
+    storage
+}
+
+/// Asserts that cloning a storage, and dropping the clone, does not move the
+/// target of the original.
+///
+/// # Panics
+/// Panics if the target moves.
+pub fn assert_clone_stable<S>(storage: &S)
+where
+    S: Storage + Clone,
+{
+    let expected = address_range(&**storage);
+    let clone = storage.clone();
+    assert_target(storage, &expected, "cloning it");
+    drop(clone);
+    assert_target(storage, &expected, "dropping a clone");
+}
+
+fn assert_target<S>(storage: &S, expected: &Range<usize>, action: &str)
+where
+    S: Deref,
+{
+    let actual = address_range(&**storage);
+    assert!(
+        actual == *expected,
+        "target of `{}` moved from {:#x}..{:#x} to {:#x}..{:#x} after {}",
+        type_name::<S>(),
+        expected.start,
+        expected.end,
+        actual.start,
+        actual.end,
+        action,
+    );
+}
+
+/// Asserts that a [`Dependant`] has the same size and alignment as its
+/// [`Dependant::Static`] form.
+///
+/// # Panics
+/// Panics if the layouts differ.
+///
+/// [`Dependant`]: crate::Dependant
+/// [`Dependant::Static`]: crate::Dependant::Static
+pub fn assert_dependant_layout<'o, D>()
+where
+    D: Dependant<'o>,
+{
+    assert!(
+        size_of::<D>() == size_of::<D::Static>() && align_of::<D>() == align_of::<D::Static>(),
+        "layout of `{}` (size {}, align {}) differs from `{}` (size {}, align {})",
+        type_name::<D>(),
+        size_of::<D>(),
+        align_of::<D>(),
+        type_name::<D::Static>(),
+        size_of::<D::Static>(),
+        align_of::<D::Static>(),
+    );
+}
+
+/// Repeatedly constructs a [`Zc`] from new owners, moving and reading it
+/// between each step, to exercise a [`Storage`] and [`Dependant`] under Miri.
+///
+/// Each iteration constructs a `Zc`, then moves it through a box, a growing
+/// vec and a swap, formatting the dependant via [`Debug`] (reading all it
+/// borrows) after each move.
+///
+/// [`Storage`]: crate::Storage
+/// [`Dependant`]: crate::Dependant
+pub fn stress<O, D, C>(owner: impl FnMut() -> O, constructor: C, iterations: usize)
+where
+    O: IntoStorage,
+    D: Dependant<'static> + Debug,
+    C: for<'o> Construct<'o, Target<O>, Dependant = D> + Clone,
+{
+    run_stress(owner, constructor, iterations, read);
+}
+
+/// Same as [`stress()`], additionally verifying that the references of the
+/// dependant lie within the owner after each move (see [`Zc::verify()`]).
+///
+/// # Panics
+/// Panics if a reference is found outside of the owner.
+pub fn stress_verified<O, D, C>(owner: impl FnMut() -> O, constructor: C, iterations: usize)
+where
+    O: IntoStorage,
+    D: Dependant<'static> + References + Debug,
+    C: for<'o> Construct<'o, Target<O>, Dependant = D> + Clone,
+    Target<O>: Extents,
+{
+    run_stress(owner, constructor, iterations, |zc| {
+        read(zc);
+        if let Err(err) = zc.verify() {
+            panic!("{}", err);
+        }
+    });
+}
+
+fn run_stress<O, D, C>(
+    mut owner: impl FnMut() -> O,
+    constructor: C,
+    iterations: usize,
+    check: fn(&Zc<O, D>),
+) where
+    O: IntoStorage,
+    D: Dependant<'static>,
+    C: for<'o> Construct<'o, Target<O>, Dependant = D> + Clone,
+{
+    let mut previous = Zc::new(owner(), constructor.clone());
+    let mut all = Vec::new();
+    for _ in 0..iterations {
+        let zc = Zc::new(owner(), constructor.clone());
+        check(&zc);
+        let mut zc = *Box::new(zc);
+        check(&zc);
+        swap(&mut zc, &mut previous);
+        check(&zc);
+        check(&previous);
+        all.push(zc);
+        all.iter().for_each(check);
+    }
+    drop(all);
+    check(&previous);
+}
+
+fn read<O, D>(zc: &Zc<O, D>)
+where
+    O: IntoStorage,
+    D: Dependant<'static> + Debug,
+{
+    write!(Sink, "{:?}", zc.value).unwrap();
+}
+
+struct Sink;
+
+impl Write for Sink {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// Asserts at compile time that a [`Dependant`] with a single lifetime is
+/// covariant over it.
+///
+/// `Zc` hands out dependants with their lifetime shortened to that of the
+/// borrow of `Zc`, which is only sound if the dependant is covariant.
+///
+/// See the [`testing`] module for an example.
+///
+/// [`Dependant`]: crate::Dependant
+/// [`testing`]: crate::testing
+#[macro_export]
+macro_rules! assert_covariant {
+    ($($dependant:ident)::+) => {
+        const _: () = {
+            #[allow(dead_code)]
+            fn covariant<'a: 'b, 'b>(dependant: $($dependant)::+<'a>) -> $($dependant)::+<'b> {
+                dependant
+            }
+        };
+    };
+}
@@ -0,0 +1,221 @@
+//! Text positions and diagnostics for dependants borrowing from a source.
+//!
+//! A [`SourceMap`] is a [`Dependant`] that maps byte offsets within a source
+//! to line and column [`Position`]s. Along with [`Zc::span_of()`], slices held
+//! by other dependants (such as parser errors) can be turned into positions
+//! and rendered as an [`Excerpt`] without manual pointer math.
+//!
+//! # Example
+//! ```
+//! use zc::text::SourceMap;
+//! use zc::Zc;
+//!
+//! fn source_map(source: &str) -> SourceMap<'_> {
+//!     SourceMap::new(source)
+//! }
+//!
+//! let data = Zc::new(String::from("let a = 1;\nlet b = @;"), source_map);
+//! let map = data.get::<SourceMap>();
+//!
+//! // Eg. the token held by a parser error.
+//! let token = &map.source()[19..20];
+//! let excerpt = map.excerpt_of(token).unwrap().with_message("unexpected token");
+//!
+//! assert_eq!(excerpt.position().to_string(), "2:9");
+//! assert_eq!(
+//!     excerpt.to_string(),
+//!     "unexpected token\n --> 2:9\n  |\n2 | let b = @;\n  |         ^\n"
+//! );
+//! ```
+//!
+//! [`Dependant`]: crate::Dependant
+//! [`Zc::span_of()`]: crate::Zc::span_of()
+
+use core::fmt::{self, Display};
+use core::iter::successors;
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::verify::address_range;
+use crate::{Dependant, Rebind, References};
+
+/// Line and column position within a source, both starting at `1`.
+///
+/// The column is counted in characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The line number.
+    pub line: usize,
+    /// The column number.
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Maps byte offsets within a source to line and column [`Position`]s.
+#[derive(Debug, Clone)]
+pub struct SourceMap<'o> {
+    source: &'o str,
+    line_starts: Vec<usize>,
+}
+
+impl<'o> SourceMap<'o> {
+    /// Construct a new `SourceMap` given the source.
+    #[must_use]
+    pub fn new(source: &'o str) -> Self {
+        let line_starts = core::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Returns the source.
+    #[must_use]
+    pub fn source(&self) -> &'o str {
+        self.source
+    }
+
+    /// Returns the number of lines within the source.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the line at the index (starting at `0`), without its line
+    /// ending.
+    #[must_use]
+    pub fn line(&self, index: usize) -> Option<&'o str> {
+        let start = *self.line_starts.get(index)?;
+        let end = self
+            .line_starts
+            .get(index + 1)
+            .map_or(self.source.len(), |next| next - 1);
+        let line = &self.source[start..end];
+        Some(line.strip_suffix('\r').unwrap_or(line))
+    }
+
+    /// Returns the position of the byte offset.
+    ///
+    /// Returns `None` if the offset is out of bounds or not on a character
+    /// boundary.
+    #[must_use]
+    pub fn position(&self, offset: usize) -> Option<Position> {
+        let index = self.line_index(offset);
+        let start = self.line_starts[index];
+        let column = self.source.get(start..offset)?.chars().count() + 1;
+        Some(Position {
+            line: index + 1,
+            column,
+        })
+    }
+
+    /// Returns the byte range of a slice within the source, or `None` if the
+    /// slice does not lie within it.
+    #[must_use]
+    pub fn span_of(&self, sub: &str) -> Option<Range<usize>> {
+        crate::private::span_within(self.source.as_bytes(), sub.as_bytes())
+    }
+
+    /// Returns an [`Excerpt`] of the line the span starts on, marking the span.
+    ///
+    /// Returns `None` if the span is out of bounds or not on character
+    /// boundaries.
+    #[must_use]
+    pub fn excerpt(&self, span: Range<usize>) -> Option<Excerpt<'o>> {
+        let _ = self.source.get(span.clone())?;
+        let position = self.position(span.start)?;
+        let line = self.line(position.line - 1)?;
+        let line_end = self.line_starts[position.line - 1] + line.len();
+        let marked = &self.source[span.start..span.end.min(line_end).max(span.start)];
+        Some(Excerpt {
+            message: None,
+            position,
+            line,
+            width: marked.chars().count().max(1),
+        })
+    }
+
+    /// Returns an [`Excerpt`] marking a slice of the source.
+    ///
+    /// Returns `None` if the slice does not lie within the source.
+    #[must_use]
+    pub fn excerpt_of(&self, sub: &str) -> Option<Excerpt<'o>> {
+        self.excerpt(self.span_of(sub)?)
+    }
+
+    fn line_index(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+}
+
+unsafe impl<'o> Dependant<'o> for SourceMap<'o> {
+    type Static = SourceMap<'static>;
+}
+
+unsafe impl<'o> Rebind<'o> for SourceMap<'static> {
+    type Dependant = SourceMap<'o>;
+}
+
+impl References for SourceMap<'_> {
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self.source));
+    }
+}
+
+/// Renders a line of a source with a caret marking a span, along with an
+/// optional message.
+///
+/// Only the first line of a span covering multiple lines is marked.
+#[derive(Debug, Clone)]
+pub struct Excerpt<'a> {
+    message: Option<&'a str>,
+    position: Position,
+    line: &'a str,
+    width: usize,
+}
+
+impl<'a> Excerpt<'a> {
+    /// Sets the message rendered before the excerpt.
+    #[must_use]
+    pub fn with_message(mut self, message: &'a str) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Returns the position the span starts at.
+    #[must_use]
+    pub fn position(&self) -> Position {
+        self.position
+    }
+}
+
+impl Display for Excerpt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = self.position.line;
+        let gutter = successors(Some(line), |n| Some(n / 10).filter(|n| *n > 0)).count();
+        if let Some(message) = self.message {
+            writeln!(f, "{message}")?;
+        }
+        writeln!(f, "{:2$}--> {}", "", self.position, gutter)?;
+        writeln!(f, "{:1$} |", "", gutter)?;
+        writeln!(f, "{} | {}", line, self.line)?;
+        writeln!(
+            f,
+            "{:3$} | {:4$}{:^<5$}",
+            "",
+            "",
+            "",
+            gutter,
+            self.position.column - 1,
+            self.width
+        )
+    }
+}
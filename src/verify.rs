@@ -0,0 +1,514 @@
+use core::fmt::{self, Display};
+use core::mem::size_of_val;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
+use core::ops::{Deref, Range};
+
+use crate::private::{Construct, TryConstruct};
+use crate::{Dependant, FromStorage, IntoStorage, Zc};
+
+/// Implemented for types that can enumerate every reference they hold.
+///
+/// Used by [`Zc::verify()`] to check that a [`Dependant`] only references
+/// data within its owner. This catches bugs in hand-written
+/// `unsafe impl Dependant` that would let foreign references be smuggled into
+/// a dependant. With the `debug-verify` feature enabled, debug builds also
+/// check this whenever a `Zc` is constructed via [`Zc::new_verified()`] or
+/// [`Zc::try_new_verified()`].
+///
+/// Can be derived with `#[derive(References)]`, which visits every field
+/// except those marked with `#[references(skip)]`, eg. fields holding
+/// references to `'static` data.
+///
+/// # Limitations
+///
+/// References are only known to lie within the owner if they fall within the
+/// memory the target of its storage declares via [`Extents`]. The target
+/// itself is always included, along with data it owns elsewhere on the heap
+/// if declared (eg. the contents of each `String` in the `[String]` target of
+/// a `Vec<String>` owner). Owners whose target does not implement [`Extents`]
+/// cannot be verified.
+///
+/// # Example
+/// ```
+/// use zc::{Dependant, References, Zc};
+///
+/// #[derive(Dependant, References)]
+/// pub struct Fields<'a> {
+///     first: &'a [u8],
+///     rest: Vec<&'a [u8]>,
+///     #[zc(check = "Copy")]
+///     #[references(skip)]
+///     label: &'static str,
+/// }
+///
+/// fn parse(bytes: &[u8]) -> Fields<'_> {
+///     let mut fields = bytes.split(|b| *b == b',');
+///     Fields {
+///         first: fields.next().unwrap(),
+///         rest: fields.collect(),
+///         label: "fields",
+///     }
+/// }
+///
+/// let data = Zc::new(b"a,b,c".to_vec(), parse);
+///
+/// assert!(data.verify().is_ok());
+/// ```
+pub trait References {
+    /// Whether the type can hold any references.
+    ///
+    /// Used to skip visiting the elements of collections.
+    const HOLDS_REFERENCES: bool = true;
+
+    /// Calls the visitor with the address range of each reference held.
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>));
+}
+
+/// Implemented for the targets of storage that can enumerate the memory they
+/// provide to a [`Dependant`].
+///
+/// Used by [`Zc::verify()`] to find where the references of a [`Dependant`]
+/// may lie. Along with the target itself, implementations visit any data the
+/// target owns outside of itself, such as the heap allocation of a `String`.
+pub trait Extents {
+    /// Whether all the memory provided lies within the value itself.
+    ///
+    /// Used to skip visiting the elements of collections.
+    const INLINE: bool = false;
+
+    /// Calls the visitor with the address range of the value, and of each
+    /// region of memory it owns outside of itself.
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>));
+}
+
+/// Error returned from [`Zc::verify()`] when a [`Dependant`] references data
+/// outside of its owner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    reference: Range<usize>,
+}
+
+impl VerifyError {
+    /// Returns the address range of the foreign reference.
+    #[must_use]
+    pub fn reference(&self) -> Range<usize> {
+        self.reference.clone()
+    }
+}
+
+impl Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "dependant references {:#x}..{:#x} outside of its owner",
+            self.reference.start, self.reference.end
+        )
+    }
+}
+
+impl<O, D> Zc<O, D>
+where
+    O: IntoStorage,
+    D: Dependant<'static>,
+{
+    /// Verifies that every reference held by the [`Dependant`] lies within
+    /// the memory the owner provides, as declared by [`Extents`].
+    ///
+    /// Empty references are ignored. References to `'static` data are
+    /// reported as foreign, so fields holding them should be skipped.
+    ///
+    /// # Errors
+    /// Returns the first reference found outside of the owner.
+    pub fn verify(&self) -> Result<(), VerifyError>
+    where
+        D: References,
+        <O::Storage as Deref>::Target: Extents,
+    {
+        let target = &*self.storage;
+        let first = address_range(target);
+        let mut result = Ok(());
+        self.value.visit_references(&mut |reference| {
+            if result.is_err() || reference.start == reference.end || within(&reference, &first) {
+                return;
+            }
+            let mut found = false;
+            target.visit_extents(&mut |extent| {
+                found = found || within(&reference, &extent);
+            });
+            if !found {
+                result = Err(VerifyError { reference });
+            }
+        });
+        result
+    }
+
+    /// Construct a new zero-copied structure given an owner and a function
+    /// for constructing the [`Dependant`], verifying it when the
+    /// `debug-verify` feature is enabled in debug builds.
+    ///
+    /// Otherwise the same as [`Zc::new()`], so the check costs nothing in
+    /// release builds.
+    ///
+    /// # Panics
+    /// Panics if verifying and [`Zc::verify()`] fails.
+    pub fn new_verified<C>(owner: O, constructor: C) -> Self
+    where
+        C: for<'o> Construct<'o, <O::Storage as Deref>::Target, Dependant = D>,
+        D: References,
+        <O::Storage as Deref>::Target: Extents,
+    {
+        let zc = Self::new(owner, constructor);
+        #[cfg(all(feature = "debug-verify", debug_assertions))]
+        zc.assert_verified();
+        zc
+    }
+
+    /// Try construct a new zero-copied structure given an owner and a
+    /// function for constructing the [`Dependant`], verifying it when the
+    /// `debug-verify` feature is enabled in debug builds.
+    ///
+    /// Otherwise the same as [`Zc::try_new()`].
+    ///
+    /// # Errors
+    /// Returns `E` if the constructor failed.
+    ///
+    /// # Panics
+    /// Panics if verifying and [`Zc::verify()`] fails.
+    pub fn try_new_verified<C, E>(owner: O, constructor: C) -> Result<Self, (E, O)>
+    where
+        O: FromStorage,
+        E: 'static,
+        C: for<'o> TryConstruct<'o, <O::Storage as Deref>::Target, Error = E, Dependant = D>,
+        D: References,
+        <O::Storage as Deref>::Target: Extents,
+    {
+        let zc = Self::try_new(owner, constructor)?;
+        #[cfg(all(feature = "debug-verify", debug_assertions))]
+        zc.assert_verified();
+        Ok(zc)
+    }
+
+    #[cfg(all(feature = "debug-verify", debug_assertions))]
+    fn assert_verified(&self)
+    where
+        D: References,
+        <O::Storage as Deref>::Target: Extents,
+    {
+        if let Err(err) = self.verify() {
+            panic!("{}", err);
+        }
+    }
+}
+
+fn within(inner: &Range<usize>, outer: &Range<usize>) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
+
+/// Returns the address range of the value referenced.
+pub(crate) fn address_range<T: ?Sized>(value: &T) -> Range<usize> {
+    let ptr: *const T = value;
+    let start = ptr.cast::<u8>() as usize;
+    start..start + size_of_val(value)
+}
+
+///////////////////////////////////////////////////////////////////////////////
+// References impl
+
+macro_rules! impl_references_none {
+    ($($ty:ty),*) => {
+        $(
+            impl References for $ty {
+                const HOLDS_REFERENCES: bool = false;
+
+                fn visit_references(&self, _visit: &mut dyn FnMut(Range<usize>)) {}
+            }
+        )*
+    };
+}
+
+impl_references_none!((), bool, char, str, f32, f64, isize, usize);
+impl_references_none!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl_references_none!(
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize
+);
+
+impl<T: ?Sized + References> References for &T {
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(*self));
+        (**self).visit_references(visit);
+    }
+}
+
+impl<T: ?Sized + References> References for &mut T {
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(&**self));
+        (**self).visit_references(visit);
+    }
+}
+
+impl<T: References> References for [T] {
+    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        if T::HOLDS_REFERENCES {
+            for item in self {
+                item.visit_references(visit);
+            }
+        }
+    }
+}
+
+impl<T: References, const N: usize> References for [T; N] {
+    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        self[..].visit_references(visit);
+    }
+}
+
+impl<T: References> References for Option<T> {
+    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        if let Some(value) = self {
+            value.visit_references(visit);
+        }
+    }
+}
+
+impl<T: References, E: References> References for Result<T, E> {
+    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES || E::HOLDS_REFERENCES;
+
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        match self {
+            Ok(value) => value.visit_references(visit),
+            Err(err) => err.visit_references(visit),
+        }
+    }
+}
+
+impl<T: References> References for Wrapping<T> {
+    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        self.0.visit_references(visit);
+    }
+}
+
+macro_rules! impl_references_tuple {
+    ($($field:tt $ty:ident),+) => {
+        impl<$($ty: References),+> References for ($($ty,)+) {
+            const HOLDS_REFERENCES: bool = $($ty::HOLDS_REFERENCES)||+;
+
+            fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+                $(self.$field.visit_references(visit);)+
+            }
+        }
+    };
+}
+
+impl_references_tuple!(0 T1);
+impl_references_tuple!(0 T1, 1 T2);
+impl_references_tuple!(0 T1, 1 T2, 2 T3);
+impl_references_tuple!(0 T1, 1 T2, 2 T3, 3 T4);
+impl_references_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5);
+impl_references_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6);
+impl_references_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7);
+impl_references_tuple!(0 T1, 1 T2, 2 T3, 3 T4, 4 T5, 5 T6, 6 T7, 7 T8);
+
+///////////////////////////////////////////////////////////////////////////////
+// Extents impl
+
+macro_rules! impl_extents_inline {
+    ($($ty:ty),*) => {
+        $(
+            impl Extents for $ty {
+                const INLINE: bool = true;
+
+                fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+                    visit(address_range(self));
+                }
+            }
+        )*
+    };
+}
+
+impl_extents_inline!((), bool, char, str, f32, f64, isize, usize);
+impl_extents_inline!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl<T: Extents> Extents for [T] {
+    const INLINE: bool = T::INLINE;
+
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        visit(address_range(self));
+        if !T::INLINE {
+            for item in self {
+                item.visit_extents(visit);
+            }
+        }
+    }
+}
+
+impl<T: Extents, const N: usize> Extents for [T; N] {
+    const INLINE: bool = T::INLINE;
+
+    fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+        self[..].visit_extents(visit);
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc {
+    use core::ops::Range;
+
+    use alloc::boxed::Box;
+    use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap};
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use super::{address_range, Extents, References};
+
+    impl_references_none!(String);
+
+    macro_rules! impl_references_iter {
+        ($($collection:ident),*) => {
+            $(
+                impl<T: References> References for $collection<T> {
+                    const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+                    fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+                        if T::HOLDS_REFERENCES {
+                            for item in self {
+                                item.visit_references(visit);
+                            }
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_references_iter!(Vec, BTreeSet, BinaryHeap);
+
+    impl<T: ?Sized + References> References for Box<T> {
+        const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            (**self).visit_references(visit);
+        }
+    }
+
+    impl<K: References, V: References> References for BTreeMap<K, V> {
+        const HOLDS_REFERENCES: bool = K::HOLDS_REFERENCES || V::HOLDS_REFERENCES;
+
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            if Self::HOLDS_REFERENCES {
+                for (key, value) in self {
+                    key.visit_references(visit);
+                    value.visit_references(visit);
+                }
+            }
+        }
+    }
+
+    impl Extents for String {
+        fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(self));
+            visit(address_range(self.as_str()));
+        }
+    }
+
+    impl<T: Extents> Extents for Vec<T> {
+        fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(self));
+            self.as_slice().visit_extents(visit);
+        }
+    }
+
+    impl<T: ?Sized + Extents> Extents for Box<T> {
+        fn visit_extents(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(self));
+            (**self).visit_extents(visit);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+mod std {
+    use core::ops::Range;
+    use std::collections::{HashMap, HashSet};
+    use std::error::Error;
+
+    use super::{References, VerifyError};
+
+    impl Error for VerifyError {}
+
+    impl<T: References, S> References for HashSet<T, S> {
+        const HOLDS_REFERENCES: bool = T::HOLDS_REFERENCES;
+
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            if T::HOLDS_REFERENCES {
+                for item in self {
+                    item.visit_references(visit);
+                }
+            }
+        }
+    }
+
+    impl<K: References, V: References, S> References for HashMap<K, V, S> {
+        const HOLDS_REFERENCES: bool = K::HOLDS_REFERENCES || V::HOLDS_REFERENCES;
+
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            if Self::HOLDS_REFERENCES {
+                for (key, value) in self {
+                    key.visit_references(visit);
+                    value.visit_references(visit);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bump")]
+mod bump {
+    use core::ops::Range;
+
+    use bumpalo::{boxed::Box, collections::String, collections::Vec};
+
+    use super::{address_range, References};
+
+    impl References for String<'_> {
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(self.as_str()));
+        }
+    }
+
+    impl<T: References> References for Vec<'_, T> {
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(self.as_slice()));
+            self.as_slice().visit_references(visit);
+        }
+    }
+
+    impl<T: ?Sized + References> References for Box<'_, T> {
+        fn visit_references(&self, visit: &mut dyn FnMut(Range<usize>)) {
+            visit(address_range(&**self));
+            (**self).visit_references(visit);
+        }
+    }
+}
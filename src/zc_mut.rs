@@ -0,0 +1,143 @@
+use core::fmt::{self, Debug};
+use core::ops::Deref;
+
+use crate::private::{ConstructMut, TryConstructMut};
+use crate::{Dependant, FromStorage, IntoStorage, Rebind, StorageMut};
+
+/// Zero-copy structure consisting of an owner and a [`Dependant`] that holds
+/// mutable borrows into it.
+///
+/// Unlike [`Zc`], the owned data is not accessible while the dependant exists
+/// and the dependant can only be accessed via [`ZcMut::with_mut()`].
+///
+/// [`Zc`]: crate::Zc
+pub struct ZcMut<O: IntoStorage, D> {
+    // SAFETY: Order of fields is important for preventing dropping the storage
+    // before the value that references it.
+    value: D,
+    storage: O::Storage,
+}
+
+impl<O, D> ZcMut<O, D>
+where
+    O: IntoStorage,
+    O::Storage: StorageMut,
+    D: Dependant<'static>,
+{
+    /// Construct a new zero-copied structure given an owner and a function for
+    /// constructing the [`Dependant`] from a mutable reference.
+    ///
+    /// # Example
+    /// ```
+    /// use zc::{Dependant, ZcMut};
+    ///
+    /// #[derive(Dependant)]
+    /// struct Header<'a> {
+    ///     name: &'a mut [u8],
+    /// }
+    ///
+    /// fn parse(bytes: &mut [u8]) -> Header<'_> {
+    ///     Header {
+    ///         name: &mut bytes[..4],
+    ///     }
+    /// }
+    ///
+    /// let mut data = ZcMut::new(b"name=value".to_vec(), parse);
+    ///
+    /// data.with_mut(|header| header.name.make_ascii_uppercase());
+    ///
+    /// assert_eq!(data.into_owner(), b"NAME=value");
+    /// ```
+    pub fn new<C>(owner: O, constructor: C) -> Self
+    where
+        C: for<'o> ConstructMut<'o, <O::Storage as Deref>::Target, Dependant = D>,
+    {
+        let mut storage = IntoStorage::into_storage(owner);
+        // Create a temporary dependant given the mutable target reference.
+        let value = unsafe { constructor.construct_mut(&mut *storage) };
+        // Construct the zero-copy structure given the raw parts.
+        Self { value, storage }
+    }
+
+    /// Try construct a new zero-copied structure given an owner and a function
+    /// for constructing the [`Dependant`] from a mutable reference.
+    ///
+    /// # Errors
+    /// Returns `E` if the constructor failed.
+    pub fn try_new<C, E>(owner: O, constructor: C) -> Result<Self, (E, O)>
+    where
+        O: FromStorage,
+        E: 'static,
+        C: for<'o> TryConstructMut<'o, <O::Storage as Deref>::Target, Error = E, Dependant = D>,
+    {
+        let mut storage = IntoStorage::into_storage(owner);
+        // Try create a temporary dependant given the mutable target reference.
+        match unsafe { constructor.try_construct_mut(&mut *storage) } {
+            Ok(value) => Ok(Self { value, storage }),
+            Err(err) => Err((err, FromStorage::from_storage(storage))),
+        }
+    }
+}
+
+impl<O, D> ZcMut<O, D>
+where
+    O: IntoStorage,
+{
+    /// Calls a function with a mutable reference to the [`Dependant`].
+    ///
+    /// The dependant is provided with a fresh lifetime, so nothing borrowed
+    /// from outside of the owner can be stored within it, and nothing borrowed
+    /// from the owner can be returned.
+    pub fn with_mut<F, R>(&mut self, f: F) -> R
+    where
+        D: for<'a> Rebind<'a>,
+        F: for<'a> FnOnce(&'a mut <D as Rebind<'a>>::Dependant) -> R,
+    {
+        let value_ptr: *mut D = core::ptr::addr_of_mut!(self.value);
+        f(unsafe { &mut *value_ptr.cast() })
+    }
+
+    /// Consumes `self` into the owner, dropping the [`Dependant`].
+    pub fn into_owner(self) -> O
+    where
+        O: FromStorage,
+    {
+        let Self { value, storage } = self;
+        drop(value);
+        FromStorage::from_storage(storage)
+    }
+}
+
+// SAFETY: See the `Send` implementation for `Zc`. A dependant holding
+// `&mut Target` is only `Send` if the target is `Send`.
+unsafe impl<O, D> Send for ZcMut<O, D>
+where
+    O: IntoStorage,
+    O::Storage: Send,
+    D: Send,
+{
+}
+
+// SAFETY: Sharing `ZcMut` only shares the dependant and the storage, as the
+// target is not accessible via a shared reference.
+unsafe impl<O, D> Sync for ZcMut<O, D>
+where
+    O: IntoStorage,
+    O::Storage: Sync,
+    D: Sync,
+{
+}
+
+impl<O, D> Debug for ZcMut<O, D>
+where
+    O: IntoStorage,
+    D: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The storage is not accessed as the dependant may be mutably
+        // borrowing it.
+        f.debug_struct("ZcMut")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
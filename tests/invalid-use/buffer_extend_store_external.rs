@@ -0,0 +1,16 @@
+use zc::{Zc, ZcBuffer};
+
+fn construct(buffer: &ZcBuffer) -> Vec<&[u8]> {
+    buffer.chunks().collect()
+}
+
+fn main() {
+    let other = vec![4, 5, 6];
+    let mut data = Zc::new(ZcBuffer::from(vec![1, 2, 3]), construct);
+
+    // should not work
+    data.extend(&[7, 8, 9], |_, chunks| chunks.push(&other[..]));
+
+    drop(other);
+    let _ = data.get::<Vec<&[u8]>>();
+}
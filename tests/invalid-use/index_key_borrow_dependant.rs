@@ -0,0 +1,18 @@
+use zc::{Dependant, IndexKey};
+
+#[derive(Dependant)]
+pub struct Entry<'a> {
+    name: String,
+    value: &'a str,
+}
+
+impl<'a> IndexKey<'a> for Entry<'a> {
+    type Key = str;
+
+    // should not work
+    fn index_key(&self) -> &'a str {
+        &self.name
+    }
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use zc::{Dependant, IntoOwned, Zc};
+
+#[derive(Dependant)]
+pub struct Leaky<'a>(&'a str);
+
+impl IntoOwned for Leaky<'static> {
+    type Owned = &'static str;
+
+    fn into_owned(self) -> Self::Owned {
+        self.0
+    }
+}
+
+fn construct(source: &str) -> Leaky<'_> {
+    Leaky(source)
+}
+
+fn main() {
+    let data = Zc::new(String::from("owned"), construct);
+
+    // should not work
+    let leaked: &'static str = data.into_owned_dependant();
+
+    println!("{}", leaked);
+}
@@ -0,0 +1,31 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::thread;
+
+use zc::Dependant;
+
+#[derive(Dependant)]
+pub struct MyStruct<'a> {
+    bytes: &'a [u8],
+    #[zc(check = "Copy")]
+    marker: PhantomData<Rc<()>>,
+}
+
+impl<'a> From<&'a [u8]> for MyStruct<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+}
+
+fn main() {
+    let owner = vec![1, 2, 3];
+    let data = zc::from!(owner, MyStruct, [u8]);
+
+    // should not work
+    thread::spawn(move || {
+        assert_eq!(data.get::<MyStruct>().bytes, &[1, 2, 3]);
+    });
+}
@@ -0,0 +1,24 @@
+use std::cell::Cell;
+use std::thread;
+
+use zc::aliasable::boxed::AliasableBox;
+use zc::Dependant;
+
+#[derive(Dependant)]
+pub struct MyStruct<'a>(#[zc(check = "Copy")] &'a Cell<u8>);
+
+impl<'a> From<&'a Cell<u8>> for MyStruct<'a> {
+    fn from(cell: &'a Cell<u8>) -> Self {
+        Self(cell)
+    }
+}
+
+fn main() {
+    let owner = AliasableBox::from(Box::new(Cell::new(1)));
+    let data = zc::from!(owner, MyStruct, Cell<u8>);
+
+    // should not work
+    thread::spawn(move || {
+        data.get::<MyStruct>().0.set(2);
+    });
+}
@@ -0,0 +1,18 @@
+use zc::{Dependant, ZcMut};
+
+#[derive(Dependant)]
+pub struct MyStruct<'a>(&'a mut [u8]);
+
+fn construct(bytes: &mut [u8]) -> MyStruct<'_> {
+    MyStruct(bytes)
+}
+
+fn main() {
+    let mut data = ZcMut::new(vec![1, 2, 3], construct);
+
+    // should not work
+    let leaked = data.with_mut(|my_struct| &mut *my_struct.0);
+
+    drop(data);
+    leaked[0] = 0;
+}
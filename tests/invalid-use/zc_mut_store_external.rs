@@ -0,0 +1,19 @@
+use zc::{Dependant, ZcMut};
+
+#[derive(Dependant)]
+pub struct MyStruct<'a>(&'a mut [u8]);
+
+fn construct(bytes: &mut [u8]) -> MyStruct<'_> {
+    MyStruct(bytes)
+}
+
+fn main() {
+    let mut other = vec![4, 5, 6];
+    let mut data = ZcMut::new(vec![1, 2, 3], construct);
+
+    // should not work
+    data.with_mut(|my_struct| my_struct.0 = &mut other[..]);
+
+    drop(other);
+    data.with_mut(|my_struct| my_struct.0[0] = 0);
+}
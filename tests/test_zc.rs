@@ -1,13 +1,19 @@
 use zc::aliasable::{boxed::AliasableBox, vec::AliasableVec};
-use zc::{Dependant, Zc};
+use zc::intern::Interner;
+use zc::{
+    BoxedOwner, Dependant, DynOwner, IndexKey, IntoOwned, LazyField, References, Zc, ZcArena,
+    ZcBuffer, ZcIndexMap, ZcMut,
+};
 
 #[derive(Dependant)]
 pub struct StructWithNoLifetime;
 
 #[derive(Dependant)]
+#[allow(dead_code)]
 pub struct ChildType<'a>(&'a ());
 
 #[derive(Dependant)]
+#[allow(dead_code)]
 pub struct StructWithOneLifetime<'a>(ChildType<'a>);
 
 #[derive(Copy, Clone)]
@@ -149,3 +155,1443 @@ fn test_aliasable_vec() {
     assert_eq!(data.get::<StructWithVecRef>(), &StructWithVecRef(&[1u8]));
     assert_eq!(AliasableVec::into_unique(data.into_owner()), vec![1]);
 }
+
+#[test]
+fn test_error() {
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug, Dependant)]
+    pub struct InvalidDigit<'a>(&'a str);
+
+    impl<'a> fmt::Display for InvalidDigit<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "invalid digit `{}`", self.0)
+        }
+    }
+
+    impl<'a> Error for InvalidDigit<'a> {}
+
+    #[derive(Debug, Dependant)]
+    pub struct ParseError<'a> {
+        input: &'a str,
+        source: InvalidDigit<'a>,
+    }
+
+    impl<'a> fmt::Display for ParseError<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "failed to parse `{}`", self.input)
+        }
+    }
+
+    // Sources must be `'static`, so only the erased dependant can return the
+    // borrowed source.
+    impl Error for ParseError<'static> {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    fn parse_digits(input: &str) -> Result<(), ParseError<'_>> {
+        match input.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => Err(ParseError {
+                input,
+                source: InvalidDigit(&input[i..=i]),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn parse(owner: String) -> Result<(), Box<dyn Error + Send + Sync>> {
+        Zc::new(owner, parse_digits)
+            .into_result()
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    assert!(parse(String::from("123")).is_ok());
+
+    let err = parse(String::from("12a3")).unwrap_err();
+    assert_eq!(err.to_string(), "failed to parse `12a3`");
+
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "invalid digit `a`");
+    assert_eq!(format!("{:?}", source), "InvalidDigit(\"a\")");
+    assert!(!source.is::<InvalidDigit<'static>>());
+    assert!(source.source().is_none());
+}
+
+#[test]
+fn test_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Zc<Vec<u8>, &'static [u8]>>();
+    assert_send_sync::<Zc<String, &'static str>>();
+    assert_send_sync::<Zc<AliasableVec<u8>, StructWithBytes<'static>>>();
+    assert_send_sync::<Zc<AliasableBox<u8>, &'static u8>>();
+}
+
+#[test]
+fn test_one_way_owner() {
+    pub struct OneWayOwner(Vec<u8>);
+
+    impl zc::IntoStorage for OneWayOwner {
+        type Storage = AliasableVec<u8>;
+
+        fn into_storage(self) -> Self::Storage {
+            AliasableVec::from(self.0)
+        }
+    }
+
+    let owner = OneWayOwner(vec![1, 2, 3]);
+    let data = zc::from!(owner, StructWithBytes, [u8]);
+
+    assert_eq!(data.as_owned(), &[1, 2, 3]);
+    assert_eq!(data.get::<StructWithBytes>(), &StructWithBytes(&[2, 3]));
+}
+
+#[test]
+fn test_boxed_owner() {
+    let owner = BoxedOwner::new([1u8, 2, 3]);
+    let data = zc::from!(owner, StructWithBytes, [u8]);
+
+    assert_eq!(data.get::<StructWithBytes>(), &StructWithBytes(&[2, 3]));
+    assert_eq!(data.into_owner().into_inner(), [1, 2, 3]);
+}
+
+#[test]
+fn test_dyn_owner() {
+    fn parse(owner: DynOwner) -> Zc<DynOwner, StructWithBytes<'static>> {
+        zc::from!(owner, StructWithBytes, [u8])
+    }
+
+    let owners = vec![
+        DynOwner::new(vec![1u8, 2, 3]),
+        DynOwner::new(String::from("abc")),
+        DynOwner::new(Box::<[u8]>::from(&[1, 2, 3][..])),
+    ];
+
+    let parsed: Vec<_> = owners.into_iter().map(parse).collect();
+
+    assert_eq!(
+        parsed[0].get::<StructWithBytes>(),
+        &StructWithBytes(&[2, 3])
+    );
+    assert_eq!(parsed[1].get::<StructWithBytes>(), &StructWithBytes(b"bc"));
+    assert_eq!(parsed[2].as_owned(), &[1, 2, 3]);
+
+    let owner = DynOwner::new_local(std::rc::Rc::<[u8]>::from(&[1, 2, 3][..]));
+    let data = zc::from!(owner, StructWithBytes, [u8]);
+    assert_eq!(data.get::<StructWithBytes>(), &StructWithBytes(&[2, 3]));
+}
+
+#[test]
+fn test_zc_mut() {
+    #[derive(Debug, Dependant)]
+    pub struct Chunks<'a>(Vec<&'a mut [u8]>);
+
+    fn split_chunks(bytes: &mut [u8]) -> Chunks<'_> {
+        Chunks(bytes.chunks_mut(2).collect())
+    }
+
+    let mut data = ZcMut::new(vec![1u8, 2, 3, 4, 5], split_chunks);
+
+    let len = data.with_mut(|chunks| {
+        for (i, chunk) in chunks.0.iter_mut().enumerate() {
+            chunk[0] = i as u8;
+        }
+        chunks.0.len()
+    });
+
+    assert_eq!(len, 3);
+    assert_eq!(data.into_owner(), vec![0, 2, 1, 4, 2]);
+}
+
+#[test]
+fn test_zc_mut_try_new() {
+    fn first_mut(bytes: &mut [u8]) -> Result<&mut u8, ()> {
+        bytes.first_mut().ok_or(())
+    }
+
+    let mut data = ZcMut::try_new(vec![1u8], first_mut).unwrap();
+    data.with_mut(|first| **first = 2);
+    assert_eq!(data.into_owner(), vec![2]);
+
+    let result = ZcMut::try_new(Vec::<u8>::new(), first_mut);
+    assert_eq!(result.unwrap_err(), ((), Vec::new()));
+}
+
+#[test]
+fn test_new_after_mut() {
+    fn remove_cr(bytes: &mut [u8]) -> &[u8] {
+        let mut len = 0;
+        for i in 0..bytes.len() {
+            if bytes[i] != b'\r' {
+                bytes[len] = bytes[i];
+                len += 1;
+            }
+        }
+        &bytes[..len]
+    }
+
+    fn lines(bytes: &[u8]) -> Vec<&[u8]> {
+        bytes.split(|b| *b == b'\n').collect()
+    }
+
+    let owner = b"a\r\nb\r\nc".to_vec();
+    let data = Zc::new_after_mut(owner, remove_cr, lines);
+
+    assert_eq!(data.get::<Vec<&[u8]>>(), &[b"a", b"b", b"c"]);
+    assert_eq!(data.as_owned(), b"a\nb\nc\nc");
+}
+
+#[test]
+fn test_try_new_after_mut() {
+    fn uppercase(bytes: &mut [u8]) -> Result<&[u8], ()> {
+        bytes.make_ascii_uppercase();
+        Ok(bytes)
+    }
+
+    fn non_empty(bytes: &[u8]) -> Result<&[u8], ()> {
+        if bytes.is_empty() {
+            Err(())
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    let data = Zc::try_new_after_mut(b"a".to_vec(), uppercase, non_empty);
+    assert_eq!(data.unwrap().get::<&[u8]>(), b"A");
+
+    let result = Zc::try_new_after_mut(Vec::new(), uppercase, non_empty);
+    assert_eq!(result.unwrap_err(), ((), Vec::new()));
+
+    let result = Zc::try_new_after_mut(b"a".to_vec(), |_: &mut [u8]| Err(()), non_empty);
+    assert_eq!(result.unwrap_err(), ((), b"a".to_vec()));
+}
+
+#[test]
+fn test_lazy_field() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static INIT_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Dependant)]
+    pub struct Document<'a> {
+        title: &'a str,
+        words: LazyField<'a, Vec<&'a str>, str>,
+    }
+
+    fn split_words(body: &str) -> Vec<&str> {
+        let _ = INIT_COUNT.fetch_add(1, Ordering::SeqCst);
+        body.split_whitespace().collect()
+    }
+
+    fn parse(s: &str) -> Document<'_> {
+        let (title, body) = s.split_at(s.find('\n').unwrap());
+        Document {
+            title,
+            words: LazyField::new(body, split_words),
+        }
+    }
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let data = Zc::new(String::from("title\nhello world"), parse);
+    let document = data.get::<Document>();
+
+    assert_send_sync(&data);
+    assert_eq!(document.title, "title");
+    assert_eq!(document.words.input(), "\nhello world");
+    assert!(document.words.get_if_init().is_none());
+    assert_eq!(document.words.get(), &["hello", "world"]);
+    assert_eq!(document.words.get(), &["hello", "world"]);
+    assert_eq!(INIT_COUNT.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_index_map() {
+    #[derive(Debug, PartialEq, Dependant)]
+    pub struct Entry<'a> {
+        name: &'a [u8],
+        value: &'a [u8],
+    }
+
+    impl<'a> IndexKey<'a> for Entry<'a> {
+        type Key = [u8];
+
+        fn index_key(&self) -> &'a [u8] {
+            self.name
+        }
+    }
+
+    fn parse(bytes: &[u8]) -> Entry<'_> {
+        let (name, value) = bytes.split_at(1);
+        Entry { name, value }
+    }
+
+    let mut map = ZcIndexMap::new();
+
+    assert!(map.insert(Zc::new(b"a1".to_vec(), parse)).is_none());
+    assert!(map.insert(Zc::new(b"b2".to_vec(), parse)).is_none());
+
+    let replaced = map.insert(Zc::new(b"a3".to_vec(), parse)).unwrap();
+    assert_eq!(replaced.into_owner(), b"a1");
+
+    assert_eq!(map.len(), 2);
+    assert!(map.contains_key(b"b"));
+    assert_eq!(map.get(b"a").unwrap().get::<Entry>().value, b"3");
+
+    let mut keys: Vec<_> = map.keys().collect();
+    keys.sort();
+    assert_eq!(keys, [b"a", b"b"]);
+
+    for (key, entry) in map.iter() {
+        assert_eq!(key, entry.get::<Entry>().name);
+    }
+
+    assert_eq!(map.remove(b"b").unwrap().into_owner(), b"b2");
+    assert!(map.remove(b"b").is_none());
+
+    let owners: Vec<_> = map.into_values().map(Zc::into_owner).collect();
+    assert_eq!(owners, [b"a3"]);
+}
+
+#[test]
+fn test_interner() {
+    #[derive(Debug, PartialEq, Dependant)]
+    pub struct Fields<'a> {
+        names: Vec<&'a str>,
+    }
+
+    fn parse(interner: &Interner<String>) -> Fields<'_> {
+        let names = interner
+            .source()
+            .split(',')
+            .map(|name| interner.intern(name))
+            .collect();
+        Fields { names }
+    }
+
+    let interner = Interner::with_source_and_chunk_capacity(String::from("ab,cde,ab,fghij,cde"), 4);
+    let data = Zc::new(interner, parse);
+    let names = &data.get::<Fields>().names;
+
+    assert_eq!(names, &["ab", "cde", "ab", "fghij", "cde"]);
+    assert!(core::ptr::eq(names[0], names[2]));
+    assert!(core::ptr::eq(names[1], names[4]));
+
+    let interner = data.as_owned();
+    assert_eq!(interner.len(), 3);
+    assert!(core::ptr::eq(interner.get("fghij").unwrap(), names[3]));
+    assert!(interner.get("xyz").is_none());
+
+    assert_eq!(data.into_owner().into_source(), "ab,cde,ab,fghij,cde");
+}
+
+#[test]
+fn test_buffer_extend() {
+    #[derive(Debug, PartialEq, Dependant)]
+    pub struct Log<'a> {
+        lines: Vec<&'a str>,
+    }
+
+    fn parse_lines(bytes: &[u8]) -> impl Iterator<Item = &str> {
+        core::str::from_utf8(bytes).unwrap().lines()
+    }
+
+    fn parse(buffer: &ZcBuffer) -> Log<'_> {
+        Log {
+            lines: buffer.chunks().flat_map(parse_lines).collect(),
+        }
+    }
+
+    let mut buffer = ZcBuffer::new();
+    let _ = buffer.push_bytes(b"first\n");
+    let mut data = Zc::new(buffer, parse);
+
+    let first = data.get::<Log>().lines[0].as_ptr();
+    let added = data.extend(b"second\nthird\n", |new_bytes, log| {
+        let count = log.lines.len();
+        log.lines.extend(parse_lines(new_bytes));
+        log.lines.len() - count
+    });
+    data.extend(b"", |new_bytes, _| assert!(new_bytes.is_empty()));
+
+    assert_eq!(added, 2);
+    assert_eq!(data.get::<Log>().lines, ["first", "second", "third"]);
+    // Existing chunks are never moved.
+    assert_eq!(data.get::<Log>().lines[0].as_ptr(), first);
+
+    let buffer = data.into_owner();
+    assert_eq!(buffer.len(), 19);
+    assert_eq!(buffer.chunk_count(), 2);
+    assert_eq!(buffer.chunk(1), Some(&b"second\nthird\n"[..]));
+}
+
+#[test]
+fn test_buffer_chunks_dependant() {
+    use zc::Chunks;
+
+    #[derive(Dependant)]
+    pub struct Reader<'a> {
+        first: &'a [u8],
+        chunks: Chunks<'a>,
+    }
+
+    fn reader(buffer: &ZcBuffer) -> Reader<'_> {
+        Reader {
+            first: buffer.chunk(0).unwrap_or_default(),
+            chunks: buffer.chunks(),
+        }
+    }
+
+    let mut data = Zc::new(ZcBuffer::from(b"a".to_vec()), reader);
+    let first = data.extend(b"b", |_, reader| reader.chunks.next().map(<[u8]>::to_vec));
+    let rest = data.extend(b"c", |_, reader| {
+        reader
+            .chunks
+            .by_ref()
+            .map(<[u8]>::to_vec)
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(first.as_deref(), Some(&b"a"[..]));
+    assert_eq!(rest, [b"b", b"c"]);
+    assert_eq!(data.get::<Reader>().first, b"a");
+    assert_eq!(data.get::<Reader>().chunks.clone().next(), None);
+}
+
+#[test]
+fn test_arena() {
+    #[derive(Debug, PartialEq, Dependant)]
+    pub struct SymbolTable<'a> {
+        symbols: Vec<(&'a str, &'a str)>,
+    }
+
+    fn collect_symbols<'a>(source: &'a str, symbols: &mut Vec<(&'a str, &'a str)>) {
+        let (module, body) = source.split_at(source.find(':').unwrap());
+        symbols.extend(body[1..].split(',').map(|name| (module, name)));
+    }
+
+    fn build(arena: &ZcArena<String>) -> SymbolTable<'_> {
+        let mut symbols = Vec::new();
+        for source in arena {
+            collect_symbols(source, &mut symbols);
+        }
+        SymbolTable { symbols }
+    }
+
+    let arena: ZcArena<String> = vec![String::from("a:x,y"), String::from("b:z")]
+        .into_iter()
+        .collect();
+    let mut data = Zc::new(arena, build);
+
+    let first = data.get::<SymbolTable>().symbols[0].0.as_ptr();
+    let count = data.add(String::from("c:w"), |source, table| {
+        collect_symbols(source, &mut table.symbols);
+        table.symbols.len()
+    });
+
+    assert_eq!(count, 4);
+    assert_eq!(
+        data.get::<SymbolTable>().symbols,
+        [("a", "x"), ("a", "y"), ("b", "z"), ("c", "w")]
+    );
+    assert_eq!(data.get::<SymbolTable>().symbols[0].0.as_ptr(), first);
+    assert_eq!(data.as_owned().get(2), Some("c:w"));
+    assert_eq!(data.into_owner().into_owners(), ["a:x,y", "b:z", "c:w"]);
+}
+
+#[cfg(feature = "bump")]
+#[test]
+fn test_bump_owner() {
+    use zc::bumpalo::collections::{String as BumpString, Vec as BumpVec};
+    use zc::BumpOwner;
+
+    #[derive(Debug, Dependant)]
+    pub enum Expr<'a> {
+        Ident(&'a str),
+        Call(&'a str, BumpVec<'a, &'a Expr<'a>>),
+        Upper(BumpString<'a>),
+    }
+
+    fn parse(owner: &BumpOwner<String>) -> Expr<'_> {
+        let (source, bump) = owner.parts();
+        let (name, args) = source.split_at(source.find('(').unwrap());
+        let mut exprs = BumpVec::new_in(bump);
+        for arg in args[1..args.len() - 1].split(',') {
+            let expr = match arg.strip_prefix('^') {
+                Some(arg) => Expr::Upper(BumpString::from_str_in(&arg.to_uppercase(), bump)),
+                None => Expr::Ident(arg),
+            };
+            exprs.push(&*bump.alloc(expr));
+        }
+        Expr::Call(name, exprs)
+    }
+
+    let data = Zc::new(BumpOwner::new(String::from("f(a,^b)")), parse);
+
+    match data.get::<Expr>() {
+        Expr::Call(name, args) => {
+            assert_eq!(*name, "f");
+            assert!(matches!(args[0], Expr::Ident("a")));
+            assert!(matches!(args[1], Expr::Upper(s) if s == "B"));
+        }
+        _ => panic!("expected call"),
+    }
+
+    let (source, bump) = data.into_owner().into_parts();
+    assert_eq!(source, "f(a,^b)");
+    assert!(bump.allocated_bytes() > 0);
+}
+
+#[test]
+fn test_shared_handles() {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use zc::{ZcBytes, ZcStr};
+
+    fn body(bytes: &[u8]) -> &[u8] {
+        &bytes[2..]
+    }
+
+    let zc = Zc::new(Arc::<[u8]>::from(&b"> hello world"[..]), body);
+    let mut bytes = ZcBytes::from(zc);
+    let owner_ptr = bytes.owner().as_ptr();
+
+    let hello = bytes.split_to(5);
+    let world = bytes.split_off(1);
+    assert_eq!(hello, b"hello"[..]);
+    assert_eq!(world, b"world"[..]);
+    assert_eq!(bytes, b" "[..]);
+    assert_eq!(hello.slice(1..=2), b"el"[..]);
+    assert_eq!(world.clone(), world);
+    assert_eq!(world.owner().as_ptr(), owner_ptr);
+
+    let set: HashSet<_> = vec![hello.clone(), hello.slice(..), world]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&b"hello"[..]));
+
+    let mut s = ZcStr::from_utf8(hello).unwrap();
+    let tail = s.split_off(2);
+    assert_eq!(format!("{}-{}", s, tail), "he-llo");
+    assert_eq!(ZcBytes::from(tail).len(), 3);
+
+    let (invalid, _) = ZcStr::from_utf8(ZcBytes::new(vec![0xFF])).unwrap_err();
+    assert_eq!(invalid.valid_up_to(), 0);
+
+    let s: ZcStr = ZcStr::from_arc(Arc::from("a=ü"));
+    let (key, value) = s.split_once('=').unwrap();
+    assert_eq!(
+        (key, value.as_str()),
+        (ZcStr::from_arc(Arc::from("a")), "ü")
+    );
+    assert!(std::panic::catch_unwind(|| value.slice(1..)).is_err());
+}
+
+#[test]
+fn test_span_and_source_map() {
+    use zc::text::{Position, SourceMap};
+
+    #[derive(Dependant)]
+    pub struct Parsed<'a> {
+        map: SourceMap<'a>,
+        error: Option<&'a str>,
+    }
+
+    fn parse(source: &str) -> Parsed<'_> {
+        let error = source.split_whitespace().find(|word| word.contains('@'));
+        Parsed {
+            map: SourceMap::new(source),
+            error,
+        }
+    }
+
+    let source = (1..=9)
+        .map(|n| format!("line{}\r\n", n))
+        .collect::<String>()
+        + "x = ä@b";
+    let data = Zc::new(source, parse);
+    let parsed = data.get::<Parsed>();
+    let error = parsed.error.unwrap();
+
+    assert_eq!(data.span_of(error), Some(67..71));
+    assert_eq!(data.span_of(&error[2..]), parsed.map.span_of(&error[2..]));
+    assert_eq!(data.span_of("ä@b"), None);
+
+    assert_eq!(parsed.map.line_count(), 10);
+    assert_eq!(parsed.map.line(0), Some("line1"));
+    assert_eq!(
+        parsed.map.position(69),
+        Some(Position {
+            line: 10,
+            column: 6
+        })
+    );
+    // Within a multi-byte character.
+    assert_eq!(parsed.map.position(68), None);
+
+    let excerpt = parsed.map.excerpt_of(error).unwrap().with_message("error");
+    assert_eq!(
+        excerpt.to_string(),
+        "error\n  --> 10:5\n   |\n10 | x = ä@b\n   |     ^^^\n"
+    );
+
+    let multiline = parsed.map.excerpt(3..10).unwrap();
+    assert_eq!(
+        multiline.to_string(),
+        " --> 1:4\n  |\n1 | line1\n  |    ^^\n"
+    );
+}
+
+#[test]
+fn test_verify() {
+    use zc::VerifyError;
+
+    #[derive(Debug, Dependant, References)]
+    pub enum Token<'a> {
+        Word(&'a str),
+        Number { digits: &'a str },
+        End,
+    }
+
+    #[derive(Debug, Dependant, References)]
+    pub struct Tokens<'a> {
+        tokens: Vec<Token<'a>>,
+        #[zc(check = "Copy")]
+        #[references(skip)]
+        label: &'static str,
+    }
+
+    fn tokenize(source: &str) -> Tokens<'_> {
+        let mut tokens: Vec<_> = source
+            .split(' ')
+            .map(|token| match token.parse::<u32>() {
+                Ok(_) => Token::Number { digits: token },
+                Err(_) => Token::Word(token),
+            })
+            .collect();
+        tokens.push(Token::End);
+        Tokens {
+            tokens,
+            label: "tokens",
+        }
+    }
+
+    let data = Zc::new(String::from("a 1 b"), tokenize);
+    assert_eq!(data.get::<Tokens>().label, "tokens");
+    assert_eq!(data.verify(), Ok(()));
+
+    // Data the target owns outside of itself.
+    #[derive(Debug, Dependant, References)]
+    pub struct Names<'a> {
+        first: &'a str,
+    }
+
+    fn first_name(names: &[String]) -> Names<'_> {
+        Names {
+            first: names[0].as_str(),
+        }
+    }
+
+    fn first_boxed_name(names: &[Box<str>]) -> Names<'_> {
+        Names { first: &names[0] }
+    }
+
+    let data = Zc::new_verified(vec![String::from("alice")], first_name);
+    assert_eq!(data.verify(), Ok(()));
+    let data = Zc::new(vec![Box::<str>::from("bob")], first_boxed_name);
+    assert_eq!(data.verify(), Ok(()));
+
+    // Data spanning owners outside of the target.
+    fn symbols(arena: &ZcArena<String>) -> Vec<&str> {
+        arena.iter().collect()
+    }
+
+    let arena: ZcArena<String> = vec![String::from("a"), String::from("b")]
+        .into_iter()
+        .collect();
+    let mut data = Zc::new(arena, symbols);
+    data.add(String::from("c"), |source, symbols| symbols.push(source));
+    assert_eq!(data.verify(), Ok(()));
+
+    // A faulty implementation that would allow smuggling in a reference.
+    #[derive(Debug, References)]
+    pub struct Smuggled<'a, 'b>(&'a str, &'b str);
+
+    unsafe impl<'o, 'b: 'o> Dependant<'o> for Smuggled<'o, 'b> {
+        type Static = Smuggled<'static, 'static>;
+    }
+
+    fn smuggle(source: &str) -> Smuggled<'_, 'static> {
+        Smuggled(source, "foreign")
+    }
+
+    let data = Zc::new(String::from("owned"), smuggle);
+    let foreign = "foreign";
+    let start = foreign.as_ptr() as usize;
+    assert_eq!(
+        data.verify().map_err(|err| err.reference()),
+        Err::<(), _>(start..start + foreign.len())
+    );
+    assert!(data
+        .verify()
+        .unwrap_err()
+        .to_string()
+        .ends_with("outside of its owner"));
+    let _: Option<VerifyError> = data.verify().err();
+
+    fn non_empty(source: &str) -> Result<&str, ()> {
+        if source.is_empty() {
+            Err(())
+        } else {
+            Ok(source)
+        }
+    }
+
+    let data = Zc::try_new_verified(String::from("a"), non_empty).unwrap();
+    assert_eq!(data.get::<&str>(), &"a");
+    let (_, owner) = Zc::try_new_verified(String::new(), non_empty)
+        .err()
+        .unwrap();
+    assert!(owner.is_empty());
+}
+
+#[cfg(all(feature = "debug-verify", debug_assertions))]
+#[test]
+#[should_panic(expected = "outside of its owner")]
+fn test_debug_verify() {
+    #[derive(References)]
+    pub struct Smuggled<'a, 'b>(&'a str, &'b str);
+
+    unsafe impl<'o, 'b: 'o> Dependant<'o> for Smuggled<'o, 'b> {
+        type Static = Smuggled<'static, 'static>;
+    }
+
+    fn smuggle(source: &str) -> Smuggled<'_, 'static> {
+        Smuggled(source, "foreign")
+    }
+
+    let _ = Zc::new_verified(String::from("owned"), smuggle);
+}
+
+#[derive(Dependant, References)]
+#[allow(dead_code)]
+pub struct GenericReferences<'a, T: 'static> {
+    #[zc(check = "Copy")]
+    value: &'a T,
+}
+
+#[test]
+fn test_debug_view() {
+    #[derive(Debug, Dependant, References)]
+    pub struct Packet<'a> {
+        header: &'a [u8],
+        payload: &'a [u8],
+    }
+
+    fn parse(bytes: &[u8]) -> Packet<'_> {
+        let (header, rest) = bytes.split_at(2);
+        Packet {
+            header,
+            payload: &rest[usize::from(header[1])..],
+        }
+    }
+
+    let owner: Vec<u8> = (0u8..20).chain(*b"zc\n").collect();
+    let data = Zc::new(owner, parse);
+
+    assert_eq!(
+        format!("{:?}", data.debug_view().references().hexdump()),
+        concat!(
+            "Zc { value: Packet { header: [0, 1], payload: [3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, ",
+            "14, 15, 16, 17, 18, 19, 122, 99, 10] }, references: [0..2, 3..23], owner: 23 bytes\n",
+            "00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|\n",
+            "          ^^ ^^    ^^ ^^ ^^ ^^ ^^  ^^ ^^ ^^ ^^ ^^ ^^ ^^ ^^\n",
+            "00000010  10 11 12 13 7a 63 0a                              |....zc.|\n",
+            "          ^^ ^^ ^^ ^^ ^^ ^^ ^^ }",
+        )
+    );
+    assert_eq!(
+        format!("{:?}", data.debug_view().references().hexdump().truncate(4)),
+        concat!(
+            "Zc { value: Packet { header: [0, 1], payload: [3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, ",
+            "14, 15, 16, 17, 18, 19, 122, 99, 10] }, references: [0..2, 3..23], owner: 23 bytes\n",
+            "00000000  00 01 02 03                                       |....|\n",
+            "          ^^ ^^    ^^\n",
+            "... 19 more bytes }",
+        )
+    );
+
+    // Storages that are not `Debug`, and dependants that are not `References`.
+    pub struct Opaque(&'static str);
+
+    fn opaque_str(opaque: &Opaque) -> &str {
+        opaque.0
+    }
+
+    let data = Zc::new(AliasableBox::from(Box::new(Opaque("a"))), opaque_str);
+
+    assert_eq!(
+        format!("{:?}", data.debug_view()),
+        r#"Zc { value: "a", .. }"#
+    );
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_testing_helpers() {
+    use std::ops::Deref;
+    use std::panic::catch_unwind;
+    use std::sync::Arc;
+    use zc::testing;
+
+    fn first_str(strings: &[String]) -> &str {
+        &strings[0]
+    }
+
+    testing::assert_storage_stable(vec![1u8, 2, 3]);
+    testing::assert_storage_stable(String::from("abc"));
+    testing::assert_clone_stable(&Arc::new([1u8, 2, 3]));
+    let _ = testing::assert_moves_stable(ZcArena::<String>::default());
+    let _ = testing::assert_moves_stable(Interner::new());
+
+    testing::assert_dependant_layout::<StructWithBytes>();
+    testing::stress(|| vec![1, 2, 3], construct_struct_with_bytes, 4);
+    testing::stress_verified(|| vec![String::from("abc")], first_str, 4);
+    zc::assert_covariant!(StructWithBytes);
+
+    // A storage with an unstable target.
+    pub struct Inline([u8; 4]);
+
+    impl Deref for Inline {
+        type Target = [u8];
+
+        fn deref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    unsafe impl zc::Storage for Inline {}
+
+    let err = catch_unwind(|| testing::assert_storage_stable(Inline([1, 2, 3, 4]))).unwrap_err();
+    assert!(err.downcast_ref::<String>().unwrap().contains("moved from"));
+
+    // A dependant with a mismatched `Static` form.
+    #[allow(dead_code)]
+    pub struct Mismatched<'a>(&'a [u8]);
+
+    unsafe impl<'o> Dependant<'o> for Mismatched<'o> {
+        type Static = ();
+    }
+
+    let err = catch_unwind(testing::assert_dependant_layout::<Mismatched>).unwrap_err();
+    assert!(err
+        .downcast_ref::<String>()
+        .unwrap()
+        .contains("differs from `()`"));
+}
+
+#[test]
+fn test_into_owned() {
+    use std::borrow::Cow;
+
+    #[derive(Debug, Dependant, References, IntoOwned)]
+    #[into_owned(derive(Debug, Clone, PartialEq))]
+    pub enum Value<'a> {
+        Text(&'a str),
+        List { items: Vec<&'a [u8]>, count: usize },
+        Missing,
+    }
+
+    #[derive(Debug, Dependant, References, IntoOwned)]
+    #[into_owned(derive(Debug, PartialEq))]
+    pub struct Entry<'a>(&'a str, Option<Value<'a>>);
+
+    fn parse(source: &str) -> Entry<'_> {
+        let line = source.lines().next().unwrap();
+        let (key, value) = line.split_once('=').unwrap();
+        let value = match value {
+            "" => Value::Missing,
+            value if value.contains(',') => {
+                let items: Vec<_> = value.split(',').map(str::as_bytes).collect();
+                let count = items.len();
+                Value::List { items, count }
+            }
+            value => Value::Text(value),
+        };
+        Entry(key, Some(value))
+    }
+
+    let data = Zc::new(String::from("key=a,b"), parse);
+    assert_eq!(
+        data.into_owned_dependant(),
+        EntryOwned(
+            String::from("key"),
+            Some(ValueOwned::List {
+                items: vec![b"a".to_vec(), b"b".to_vec()],
+                count: 2
+            })
+        )
+    );
+
+    let entry: EntryOwned = Zc::new(String::from("key="), parse).into_owned_dependant();
+    assert_eq!(entry.1, Some(ValueOwned::Missing));
+
+    // Detach when the owner is much larger than the view.
+    let mut body = String::from("key=value\n");
+    body.push_str(&"-".repeat(1000));
+    let data = Zc::new(body, parse).detach_if(1000).unwrap_err();
+    let entry = data.detach_if(64).unwrap();
+    assert_eq!(entry.1, Some(ValueOwned::Text(String::from("value"))));
+
+    // Generic dependants.
+    #[derive(Dependant, IntoOwned)]
+    pub struct Tagged<'a, T: Copy + 'static> {
+        #[zc(check = "Copy")]
+        tag: T,
+        name: &'a str,
+    }
+
+    fn tagged(source: &str) -> Tagged<'_, u8> {
+        Tagged {
+            tag: 1,
+            name: source,
+        }
+    }
+
+    let tagged = Zc::new(String::from("name"), tagged).into_owned_dependant();
+    assert_eq!((tagged.tag, tagged.name.as_str()), (1, "name"));
+    assert_eq!(Cow::Borrowed("a").into_owned(), "a");
+    assert_eq!((1u8, "a").into_owned(), (1u8, String::from("a")));
+}
+
+#[test]
+fn test_cache() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use zc::ZcCache;
+
+    fn words(source: &str) -> Vec<&str> {
+        source.split(' ').collect()
+    }
+
+    fn load(key: &u32) -> Zc<String, Vec<&'static str>> {
+        Zc::new(format!("doc {}", key), words)
+    }
+
+    static EVICTED: AtomicUsize = AtomicUsize::new(0);
+
+    let cache = ZcCache::new(2).on_evict(|key: &u32, zc| {
+        assert_eq!(zc.get::<Vec<&str>>()[1], key.to_string());
+        let _ = EVICTED.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let first = cache.get_or_insert_with(1, load);
+    let _ = cache.get_or_insert_with(2, load);
+    assert!(Arc::ptr_eq(
+        &first,
+        &cache.get_or_insert_with(1, |_| unreachable!())
+    ));
+    // `2` is now the least recently used.
+    let _ = cache.get_or_insert_with(3, load);
+
+    assert_eq!(EVICTED.load(Ordering::SeqCst), 1);
+    assert!(cache.contains_key(&1) && cache.contains_key(&3) && !cache.contains_key(&2));
+    assert_eq!(cache.len(), 2);
+
+    let err = cache.try_get_or_insert_with(4, |_| Err("missing"));
+    assert_eq!(err.unwrap_err(), "missing");
+    assert!(cache.insert(3, load(&3)).is_some());
+    assert!(cache.remove(&1).is_some());
+    assert_eq!(EVICTED.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        format!("{:?}", cache),
+        "ZcCache { len: 1, weight: 1, max_weight: 2, .. }"
+    );
+
+    // Bounded by the bytes of each owner, shared across threads.
+    let cache = Arc::new(ZcCache::with_max_bytes(12));
+    let handles: Vec<_> = (0..4u32)
+        .map(|key| {
+            let cache = Arc::clone(&cache);
+            std::thread::spawn(move || cache.get_or_insert_with(key, load).get::<Vec<&str>>().len())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 2);
+    }
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.weight(), 10);
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_oversize_entry() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use zc::ZcCache;
+
+    fn first(source: &str) -> &str {
+        source.split(' ').next().unwrap_or_default()
+    }
+
+    static EVICTED: AtomicUsize = AtomicUsize::new(0);
+
+    let cache = ZcCache::with_max_bytes(8).on_evict(|_: &&str, _| {
+        let _ = EVICTED.fetch_add(1, Ordering::SeqCst);
+    });
+    let _ = cache.get_or_insert_with("small", |_| Zc::new(String::from("a b"), first));
+
+    // An entry larger than the cache is returned, but neither inserted nor
+    // evicting the entries already cached.
+    let large = cache.get_or_insert_with("large", |_| {
+        Zc::new(String::from("too large to fit"), first)
+    });
+    assert_eq!(large.get::<&str>(), &"too");
+    assert!(!cache.contains_key("large"));
+    assert!(cache.contains_key("small"));
+    assert!(cache
+        .insert("small", Zc::new(String::from("also too large"), first))
+        .is_some());
+    assert!(cache.is_empty());
+    assert_eq!(cache.weight(), 0);
+    assert_eq!(EVICTED.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_cache_panicking_key() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use zc::ZcCache;
+
+    #[derive(Hash, PartialEq, Eq)]
+    struct Key(u32);
+
+    impl Clone for Key {
+        fn clone(&self) -> Self {
+            assert!(self.0 != 0, "cannot clone key");
+            Self(self.0)
+        }
+    }
+
+    fn whole(source: &str) -> &str {
+        source
+    }
+
+    fn entry(source: &str) -> Zc<String, &'static str> {
+        Zc::new(String::from(source), whole)
+    }
+
+    let cache = ZcCache::new(1);
+    let result = catch_unwind(AssertUnwindSafe(|| cache.insert(Key(0), entry("a"))));
+
+    // The state is left as it was before the panic.
+    assert!(result.is_err());
+    assert!(cache.is_empty());
+    assert_eq!(cache.weight(), 0);
+    let _ = cache.insert(Key(1), entry("b"));
+    let _ = cache.insert(Key(2), entry("c"));
+    assert_eq!((cache.len(), cache.weight()), (1, 1));
+}
+
+#[cfg(feature = "reload")]
+#[test]
+fn test_reloader() {
+    use std::fs::{self, File};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+    use zc::{ReloadError, ZcReloader};
+
+    #[derive(Dependant)]
+    pub struct Routes<'a> {
+        routes: Vec<(&'a str, &'a str)>,
+    }
+
+    fn parse(source: &str) -> Result<Routes<'_>, String> {
+        let routes = source
+            .lines()
+            .map(|line| {
+                line.split_once(' ')
+                    .ok_or_else(|| format!("invalid: {line}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Routes { routes })
+    }
+
+    fn write(path: &std::path::Path, contents: &str, secs: u64) {
+        fs::write(path, contents).unwrap();
+        let file = File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("zc-reloader-{}.conf", std::process::id()));
+    write(&path, "/ index", 1);
+
+    let reloader = ZcReloader::new(String::new(), parse).unwrap();
+    assert!(reloader.load().get::<Routes>().routes.is_empty());
+    assert!(reloader.poll_file(&path, fs::read_to_string).unwrap());
+    assert!(!reloader.poll_file(&path, fs::read_to_string).unwrap());
+    let first = reloader.load();
+    assert_eq!(first.get::<Routes>().routes, [("/", "index")]);
+
+    // A failed parse keeps the current version, and is not retried until the
+    // file is modified again.
+    write(&path, "/ index\n/broken", 2);
+    let err = reloader.poll_file(&path, fs::read_to_string).unwrap_err();
+    assert!(matches!(&err, ReloadError::Construct(err) if err == "invalid: /broken"));
+    assert!(!reloader.poll_file(&path, fs::read_to_string).unwrap());
+    assert!(Arc::ptr_eq(&first, &reloader.load()));
+
+    write(&path, "/ index\n/about about", 3);
+    assert!(reloader.poll_file(&path, fs::read_to_string).unwrap());
+    assert_eq!(reloader.load().get::<Routes>().routes.len(), 2);
+    // Existing snapshots are unaffected.
+    assert_eq!(first.get::<Routes>().routes.len(), 1);
+
+    fs::remove_file(&path).unwrap();
+    let err = reloader.poll_file(&path, fs::read_to_string).unwrap_err();
+    assert!(matches!(err, ReloadError::Io(_)));
+    assert_eq!(reloader.load().get::<Routes>().routes.len(), 2);
+
+    let (err, owner) = reloader.reload(String::from("x")).err().unwrap();
+    assert_eq!((err.as_str(), owner.as_str()), ("invalid: x", "x"));
+
+    // Owners that cannot be recovered from their storage.
+    pub struct OneWayOwner(String);
+
+    impl zc::IntoStorage for OneWayOwner {
+        type Storage = zc::aliasable::string::AliasableString;
+
+        fn into_storage(self) -> Self::Storage {
+            self.0.into()
+        }
+    }
+
+    let reloader = ZcReloader::new(OneWayOwner(String::from("/ index")), parse).unwrap();
+    let err = reloader.reload_or_drop(OneWayOwner(String::from("x")));
+    assert_eq!(err.err().unwrap(), "invalid: x");
+    let routes = reloader.reload_or_drop(OneWayOwner(String::from("/a a\n/b b")));
+    assert_eq!(routes.unwrap().get::<Routes>().routes.len(), 2);
+}
+
+#[test]
+fn test_reader_and_records() {
+    use std::io::{BufReader, ErrorKind};
+    use zc::io::{LengthPrefix, RecordError, Records};
+
+    fn fields(bytes: &[u8]) -> Vec<&[u8]> {
+        bytes.split(|b| *b == b',').collect()
+    }
+
+    fn non_empty(bytes: &[u8]) -> Result<&[u8], &'static str> {
+        if bytes.is_empty() {
+            Err("empty")
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    let data = Zc::from_reader(&b"a,b,c"[..], fields).unwrap();
+    assert_eq!(data.get::<Vec<&[u8]>>(), &[b"a", b"b", b"c"]);
+
+    // Delimited, with a final record missing its delimiter.
+    let reader = BufReader::with_capacity(4, &b"first\n\nsecond record\nlast"[..]);
+    let mut records = Records::delimited(reader, b'\n', non_empty);
+
+    let first = records.next().unwrap().unwrap();
+    assert_eq!(first.get::<&[u8]>(), b"first");
+    let owner = first.into_owner();
+    let owner_ptr = owner.as_ptr();
+    records.recycle(owner);
+    assert_eq!(records.pooled(), 1);
+
+    // The failed record is read into the recycled owner, and returned with it.
+    match records.next().unwrap() {
+        Err(RecordError::Construct(err, bytes)) => {
+            assert_eq!((err, bytes.as_ptr()), ("empty", owner_ptr));
+            assert!(bytes.is_empty());
+            records.recycle(bytes);
+        }
+        _ => panic!("expected construct error"),
+    }
+    let second = records.next().unwrap().unwrap();
+    assert_eq!(second.get::<&[u8]>(), b"second record");
+    assert_eq!(records.next().unwrap().unwrap().get::<&[u8]>(), b"last");
+    assert!(records.next().is_none());
+    assert_eq!(records.pooled(), 1);
+
+    // Length prefixed, ending within a record.
+    let source = b"\x00\x03abc\x00\x00\x00\x05de";
+    let mut records = Records::length_prefixed(&source[..], LengthPrefix::U16Be, non_empty);
+    assert_eq!(records.next().unwrap().unwrap().get::<&[u8]>(), b"abc");
+    assert!(matches!(
+        records.next(),
+        Some(Err(RecordError::Construct("empty", bytes))) if bytes.is_empty()
+    ));
+    match records.next() {
+        Some(Err(RecordError::Io(err))) => assert_eq!(err.kind(), ErrorKind::UnexpectedEof),
+        _ => panic!("expected io error"),
+    }
+    assert!(records.next().is_none());
+
+    let source = b"\x02\x00\x00\x00hi";
+    let records = Records::length_prefixed(&source[..], LengthPrefix::U32Le, non_empty);
+    let records: Vec<_> = records.map(|record| record.unwrap()).collect();
+    assert_eq!(records[0].get::<&[u8]>(), b"hi");
+}
+
+#[test]
+fn test_new_async() {
+    use std::future::Future;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Dependant)]
+    pub struct Request<'a> {
+        method: &'a [u8],
+        body: &'a [u8],
+    }
+
+    async fn parse(bytes: &[u8]) -> Request<'_> {
+        let split = bytes.iter().position(|b| *b == b' ').unwrap();
+        let method = &bytes[..split];
+        // The borrow of the owner is held across the await point.
+        tokio::task::yield_now().await;
+        Request {
+            method,
+            body: &bytes[split + 1..],
+        }
+    }
+
+    fn assert_send<T: Send>(value: T) -> T {
+        value
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let future = assert_send(Zc::new_async(b"POST a=1".to_vec(), parse));
+    let data = runtime.block_on(future);
+    let request = data.get::<Request>();
+    assert_eq!((request.method, request.body), (&b"POST"[..], &b"a=1"[..]));
+
+    // Dropping an incomplete future drops the owner.
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Owner(Vec<u8>);
+
+    impl Drop for Owner {
+        fn drop(&mut self) {
+            let _ = DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl AsRef<[u8]> for Owner {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    let future = Zc::new_async(BoxedOwner::new(Owner(b"GET ".to_vec())), parse);
+    let mut future = Box::pin(future);
+    let waker = std::task::Waker::noop();
+    let poll = future
+        .as_mut()
+        .poll(&mut std::task::Context::from_waker(waker));
+    assert!(poll.is_pending());
+    drop(future);
+    assert_eq!(DROPPED.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn test_from_async_read() {
+    use std::io::ErrorKind;
+
+    fn fields(bytes: &[u8]) -> Vec<&[u8]> {
+        bytes.split(|b| *b == b'&').collect()
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let body = &b"a=1&b=2"[..];
+        let data = Zc::from_async_read(body, 7, fields).await.unwrap();
+        assert_eq!(data.get::<Vec<&[u8]>>(), &[b"a=1", b"b=2"]);
+
+        let err = Zc::from_async_read(body, 6, fields).await.err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        let data = Zc::from_async_read(&b""[..], 0, fields).await.unwrap();
+        assert_eq!(data.get::<Vec<&[u8]>>(), &[b""]);
+    });
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn test_codec() {
+    use bytes::{Bytes, BytesMut};
+    use std::io::ErrorKind;
+    use tokio::io::AsyncWriteExt;
+    use tokio_stream::StreamExt;
+    use tokio_util::codec::{Decoder, FramedRead};
+    use zc::io::LengthPrefix;
+    use zc::{CodecError, ZcCodec};
+
+    fn words(frame: &[u8]) -> Result<Vec<&[u8]>, &'static str> {
+        if frame.is_empty() {
+            Err("empty")
+        } else {
+            Ok(frame.split(|b| *b == b' ').collect())
+        }
+    }
+
+    // Frames are split from the read buffer without copying.
+    let mut codec = ZcCodec::delimited(b'\n', words);
+    let mut src = BytesMut::from(&b"a b\n\nc"[..]);
+    let start = src.as_ptr();
+    let frame = codec.decode(&mut src).unwrap().unwrap();
+    assert_eq!(frame.as_owned().as_ptr(), start);
+    assert_eq!(frame.get::<Vec<&[u8]>>(), &[b"a", b"b"]);
+    match codec.decode(&mut src) {
+        Err(CodecError::Construct("empty", frame)) => assert!(frame.is_empty()),
+        _ => panic!("expected construct error"),
+    }
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    let frame = codec.decode_eof(&mut src).unwrap().unwrap();
+    assert_eq!(frame.get::<Vec<&[u8]>>(), &[b"c"]);
+    assert!(codec.decode_eof(&mut src).unwrap().is_none());
+
+    let mut codec = ZcCodec::delimited(b'\n', words).max_frame_len(4);
+    let mut src = BytesMut::from(&b"abcde"[..]);
+    match codec.decode(&mut src) {
+        Err(CodecError::Io(err)) => assert_eq!(err.kind(), ErrorKind::InvalidData),
+        _ => panic!("expected io error"),
+    }
+
+    let mut codec = ZcCodec::length_prefixed(LengthPrefix::U32Be, words).max_frame_len(4);
+    let mut src = BytesMut::from(&b"\x00\x00\x01\x00"[..]);
+    assert!(matches!(codec.decode(&mut src), Err(CodecError::Io(_))));
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let (mut client, server) = tokio::io::duplex(8);
+        let writer = tokio::spawn(async move {
+            // Frames span multiple writes and reads.
+            for chunk in [&b"\x00\x05hel"[..], b"lo\x00\x05a", b" b c\x00\x01"] {
+                client.write_all(chunk).await.unwrap();
+            }
+        });
+        let codec = ZcCodec::length_prefixed(LengthPrefix::U16Be, words);
+        let frames: Vec<_> = FramedRead::new(server, codec).collect().await;
+        writer.await.unwrap();
+
+        assert_eq!(frames.len(), 3);
+        let frame = frames[0].as_ref().unwrap();
+        assert_eq!(frame.get::<Vec<&[u8]>>(), &[b"hello"]);
+        assert_eq!(frame.as_owned(), &Bytes::from_static(b"hello"));
+        let frame = frames[1].as_ref().unwrap();
+        assert_eq!(frame.get::<Vec<&[u8]>>(), &[b"a", b"b", b"c"]);
+        match &frames[2] {
+            Err(CodecError::Io(err)) => assert_eq!(err.kind(), ErrorKind::UnexpectedEof),
+            _ => panic!("expected io error"),
+        }
+    });
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_bytes_owner() {
+    use bytes::{Bytes, BytesMut};
+
+    fn fields(bytes: &[u8]) -> Vec<&[u8]> {
+        bytes.split(|b| *b == b',').collect()
+    }
+
+    fn non_empty(bytes: &[u8]) -> Result<&[u8], ()> {
+        if bytes.is_empty() {
+            Err(())
+        } else {
+            Ok(bytes)
+        }
+    }
+
+    let owner = Bytes::from(b"a,bc,d".to_vec());
+    let shared = owner.clone();
+    let data = Zc::new(owner, fields);
+    assert_eq!(data.as_owned().as_ptr(), shared.as_ptr());
+    assert_eq!(data.verify(), Ok(()));
+
+    let bc = data.slice_owner_ref(data.get::<Vec<&[u8]>>()[1]).unwrap();
+    assert_eq!(bc, &b"bc"[..]);
+    assert_eq!(bc.as_ptr(), shared[2..].as_ptr());
+    assert_eq!(data.slice_owner(..1), &b"a"[..]);
+    assert!(data.slice_owner_ref(b"bc").is_none());
+    assert_eq!(data.into_owner(), shared);
+
+    // `BytesMut` is frozen without copying.
+    let mut owner = BytesMut::with_capacity(16);
+    owner.extend_from_slice(b"x,y");
+    let start = owner.as_ptr();
+    let data = Zc::new(owner, fields);
+    assert_eq!(data.as_owned().as_ptr(), start);
+    assert_eq!(data.slice_owner(2..), &b"y"[..]);
+
+    let (_, owner) = Zc::try_new(Bytes::new(), non_empty).err().unwrap();
+    assert!(owner.is_empty());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_map_file() {
+    use memmap2::{Mmap, MmapOptions};
+    use std::fs;
+    use std::io::ErrorKind;
+
+    fn entries(index: &[u8]) -> Vec<&[u8]> {
+        index.split(|b| *b == b'\n').collect()
+    }
+
+    let path = std::env::temp_dir().join(format!("zc-map-file-{}.idx", std::process::id()));
+    fs::write(&path, "alpha\nbeta\ngamma").unwrap();
+
+    // SAFETY: The file is not modified while mapped.
+    let index = unsafe { Zc::map_file(&path, entries) }.unwrap();
+    assert_eq!(
+        index.get::<Vec<&[u8]>>(),
+        &[&b"alpha"[..], b"beta", b"gamma"]
+    );
+    assert_eq!(index.verify(), Ok(()));
+    let mmap: Mmap = index.into_owner();
+    assert_eq!(mmap.len(), 16);
+
+    let mut options = MmapOptions::new();
+    let _ = options.offset(6).len(4);
+    // SAFETY: The file is not modified while mapped.
+    let index = unsafe { Zc::map_file_with(&path, &options, entries) }.unwrap();
+    assert_eq!(index.get::<Vec<&[u8]>>(), &[b"beta"]);
+    // SAFETY: The file is not modified while mapped.
+    let copy = unsafe { Zc::map_file_copy(&path, &options, entries) }.unwrap();
+    assert_eq!(copy.get::<Vec<&[u8]>>(), &[b"beta"]);
+
+    drop((mmap, index, copy));
+    fs::write(&path, "").unwrap();
+    // SAFETY: The file is not modified while mapped.
+    let index = unsafe { Zc::map_file(&path, entries) }.unwrap();
+    assert_eq!(index.get::<Vec<&[u8]>>(), &[b""]);
+
+    drop(index);
+    fs::remove_file(&path).unwrap();
+    // SAFETY: The file does not exist.
+    let err = unsafe { Zc::map_file(&path, entries) }.err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
@@ -1,12 +1,12 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, quote_spanned};
+use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
 use syn::{
-    parse_macro_input, Attribute, Data, DeriveInput, Field, GenericParam, Ident, Lifetime,
-    LifetimeDef,
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Field, Fields, GenericParam,
+    Ident, Index, Lifetime, LifetimeDef, WherePredicate,
 };
 
 #[proc_macro_derive(Dependant, attributes(zc))]
@@ -55,10 +55,340 @@ pub fn derive_dependant(input: TokenStream) -> TokenStream {
         unsafe impl #impl_dependant_generics ::zc::Dependant<#dependant_lifetime> for #name #ty_generics #where_clause {
             type Static = #name #ty_generic_static;
         }
+
+        unsafe impl #impl_dependant_generics ::zc::Rebind<#dependant_lifetime> for #name #ty_generic_static #where_clause {
+            type Dependant = #name #ty_generics;
+        }
     };
     TokenStream::from(dependant_impl)
 }
 
+#[proc_macro_derive(References, attributes(references))]
+pub fn derive_references(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let visits = match impl_visit_references(&input) {
+        Ok(visits) => visits,
+        Err(err) => return TokenStream::from(err),
+    };
+    let type_params: Vec<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::zc::References));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let references_impl = quote! {
+        impl #impl_generics ::zc::References for #name #ty_generics #where_clause {
+            fn visit_references(&self, visit: &mut dyn ::core::ops::FnMut(::core::ops::Range<usize>)) {
+                #visits
+            }
+        }
+    };
+    TokenStream::from(references_impl)
+}
+
+fn impl_visit_references(input: &DeriveInput) -> Result<TokenStream2, TokenStream2> {
+    match &input.data {
+        Data::Struct(v) => {
+            let (pattern, visits) = visit_fields(&v.fields)?;
+            Ok(quote! {
+                let Self #pattern = self;
+                #visits
+            })
+        }
+        Data::Enum(v) => {
+            let mut arms = TokenStream2::new();
+            for variant in &v.variants {
+                let variant_name = &variant.ident;
+                let (pattern, visits) = visit_fields(&variant.fields)?;
+                arms.extend(quote! {
+                    Self::#variant_name #pattern => { #visits }
+                });
+            }
+            Ok(quote! {
+                #[allow(unreachable_code)]
+                match self { #arms }
+            })
+        }
+        Data::Union(_) => Err(
+            quote_spanned! { input.span() => compile_error!("deriving `zc::References` is not supported for unions"); },
+        ),
+    }
+}
+
+fn visit_fields(fields: &Fields) -> Result<(TokenStream2, TokenStream2), TokenStream2> {
+    let mut patterns = Vec::new();
+    let mut visits = TokenStream2::new();
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__field_{}", index);
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(index);
+                quote!(#index)
+            }
+        };
+        if parse_references_skip(field)? {
+            patterns.push(quote!(#member: _));
+        } else {
+            patterns.push(quote!(#member: #binding));
+            visits.extend(quote! {
+                ::zc::References::visit_references(#binding, visit);
+            });
+        }
+    }
+    let pattern = match fields {
+        Fields::Unit => quote!(),
+        _ => quote!({ #(#patterns,)* }),
+    };
+    Ok((pattern, visits))
+}
+
+fn parse_references_skip(field: &Field) -> Result<bool, TokenStream2> {
+    let references_attr_ident = Ident::new("references", Span::call_site());
+    let mut skip = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.get_ident() == Some(&references_attr_ident))
+    {
+        match attr.tokens.to_string().as_str() {
+            "(skip)" => skip = true,
+            _ => {
+                return Err(
+                    quote_spanned! { attr.span() => compile_error!("Unknown `references` options"); },
+                )
+            }
+        }
+    }
+    Ok(skip)
+}
+
+#[proc_macro_derive(IntoOwned, attributes(into_owned))]
+pub fn derive_into_owned(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let vis = &input.vis;
+    let owned_name = format_ident!("{}Owned", name);
+    let mut lifetimes = input.generics.lifetimes();
+    let lifetime = lifetimes.next().map(|def| def.lifetime.ident.clone());
+    if lifetimes.next().is_some() {
+        let message = format!(
+            "multiple lifetimes on `{}` when only a single is valid on a `zc::IntoOwned`",
+            name
+        );
+        return TokenStream::from(
+            quote_spanned! { input.generics.span() => compile_error!(#message); },
+        );
+    }
+    let owned_derives = match parse_into_owned_attrs(&input) {
+        Ok(derives) => derives,
+        Err(err) => return TokenStream::from(err),
+    };
+    let owned_field = |field: &Field| {
+        let ty = replace_lifetime(field.ty.to_token_stream(), lifetime.as_ref());
+        quote!(<#ty as ::zc::IntoOwned>::Owned)
+    };
+    let mut owned_generics = input.generics.clone();
+    owned_generics.params = input
+        .generics
+        .params
+        .iter()
+        .filter(|param| !matches!(param, GenericParam::Lifetime(_)))
+        .cloned()
+        .collect();
+    if let Some(where_clause) = &mut owned_generics.where_clause {
+        let predicates =
+            replace_lifetime(where_clause.predicates.to_token_stream(), lifetime.as_ref());
+        where_clause.predicates = parse_quote!(#predicates);
+    }
+    let mut input_generics = input.generics.clone();
+    if input.generics.type_params().next().is_some() {
+        for field in data_fields(&input.data) {
+            let ty = replace_lifetime(field.ty.to_token_stream(), lifetime.as_ref());
+            let predicate: WherePredicate = parse_quote!(#ty: ::zc::IntoOwned);
+            owned_generics
+                .make_where_clause()
+                .predicates
+                .push(predicate.clone());
+            input_generics
+                .make_where_clause()
+                .predicates
+                .push(predicate);
+        }
+    }
+    let (_, owned_ty_generics, owned_where_clause) = owned_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input_generics.split_for_impl();
+    let (owned_body, into_owned_body) = match &input.data {
+        Data::Struct(v) => {
+            let (definition, conversion) = owned_fields(&v.fields, &owned_field);
+            let pattern = bind_fields(&v.fields);
+            let owned_body = match v.fields {
+                Fields::Named(_) => {
+                    quote!(struct #owned_name #owned_generics #owned_where_clause #definition)
+                }
+                _ => quote!(struct #owned_name #owned_generics #definition #owned_where_clause;),
+            };
+            (
+                owned_body,
+                quote! {
+                    let Self #pattern = self;
+                    #owned_name #conversion
+                },
+            )
+        }
+        Data::Enum(v) => {
+            let mut variants = TokenStream2::new();
+            let mut arms = TokenStream2::new();
+            for variant in &v.variants {
+                let variant_name = &variant.ident;
+                let (definition, conversion) = owned_fields(&variant.fields, &owned_field);
+                let pattern = bind_fields(&variant.fields);
+                variants.extend(quote!(#variant_name #definition,));
+                arms.extend(quote! {
+                    Self::#variant_name #pattern => #owned_name::#variant_name #conversion,
+                });
+            }
+            (
+                quote!(enum #owned_name #owned_generics #owned_where_clause { #variants }),
+                quote!(match self { #arms }),
+            )
+        }
+        Data::Union(_) => {
+            return TokenStream::from(
+                quote_spanned! { input.span() => compile_error!("deriving `zc::IntoOwned` is not supported for unions"); },
+            );
+        }
+    };
+    let doc = format!("Owned counterpart of [`{}`].", name);
+    let into_owned_impl = quote! {
+        #[doc = #doc]
+        #(#owned_derives)*
+        #vis #owned_body
+
+        impl #impl_generics ::zc::IntoOwned for #name #ty_generics #where_clause {
+            type Owned = #owned_name #owned_ty_generics;
+
+            fn into_owned(self) -> Self::Owned {
+                #into_owned_body
+            }
+        }
+    };
+    TokenStream::from(into_owned_impl)
+}
+
+fn data_fields(data: &Data) -> Vec<&Field> {
+    match data {
+        Data::Struct(v) => v.fields.iter().collect(),
+        Data::Enum(v) => v.variants.iter().flat_map(|v| v.fields.iter()).collect(),
+        Data::Union(_) => Vec::new(),
+    }
+}
+
+fn owned_fields(
+    fields: &Fields,
+    owned_field: &dyn Fn(&Field) -> TokenStream2,
+) -> (TokenStream2, TokenStream2) {
+    let mut definitions = Vec::new();
+    let mut conversions = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let binding = format_ident!("__field_{}", index);
+        let vis = &field.vis;
+        let docs = field.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+        let ty = owned_field(field);
+        match &field.ident {
+            Some(ident) => {
+                definitions.push(quote!(#(#docs)* #vis #ident: #ty));
+                conversions.push(quote!(#ident: ::zc::IntoOwned::into_owned(#binding)));
+            }
+            None => {
+                definitions.push(quote!(#(#docs)* #vis #ty));
+                conversions.push(quote!(::zc::IntoOwned::into_owned(#binding)));
+            }
+        }
+    }
+    match fields {
+        Fields::Named(_) => (quote!({ #(#definitions,)* }), quote!({ #(#conversions,)* })),
+        Fields::Unnamed(_) => (quote!(( #(#definitions,)* )), quote!(( #(#conversions,)* ))),
+        Fields::Unit => (quote!(), quote!()),
+    }
+}
+
+fn bind_fields(fields: &Fields) -> TokenStream2 {
+    let patterns = fields.iter().enumerate().map(|(index, field)| {
+        let binding = format_ident!("__field_{}", index);
+        match &field.ident {
+            Some(ident) => quote!(#ident: #binding),
+            None => {
+                let index = Index::from(index);
+                quote!(#index: #binding)
+            }
+        }
+    });
+    match fields {
+        Fields::Unit => quote!(),
+        _ => quote!({ #(#patterns,)* }),
+    }
+}
+
+/// Replaces the lifetime within the tokens with `'static`.
+fn replace_lifetime(tokens: TokenStream2, lifetime: Option<&Ident>) -> TokenStream2 {
+    let lifetime = match lifetime {
+        Some(lifetime) => lifetime,
+        None => return tokens,
+    };
+    let mut replaced = Vec::new();
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    replace_lifetime(group.stream(), Some(lifetime)),
+                );
+                new_group.set_span(group.span());
+                replaced.push(TokenTree::Group(new_group));
+            }
+            TokenTree::Ident(ident)
+                if ident == *lifetime
+                    && matches!(replaced.last(), Some(TokenTree::Punct(p)) if p.as_char() == '\'') =>
+            {
+                replaced.push(TokenTree::Ident(Ident::new("static", ident.span())));
+            }
+            token => replaced.push(token),
+        }
+    }
+    replaced.into_iter().collect()
+}
+
+fn parse_into_owned_attrs(input: &DeriveInput) -> Result<Vec<TokenStream2>, TokenStream2> {
+    let mut derives = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("into_owned"))
+    {
+        let error =
+            || quote_spanned! { attr.span() => compile_error!("Unknown `into_owned` options"); };
+        let tokens: Vec<_> = match attr.tokens.clone().into_iter().next() {
+            Some(TokenTree::Group(group)) => group.stream().into_iter().collect(),
+            _ => return Err(error()),
+        };
+        match tokens.as_slice() {
+            [TokenTree::Ident(ident), TokenTree::Group(group)] if ident == "derive" => {
+                derives.push(quote!(#[derive #group]));
+            }
+            _ => return Err(error()),
+        }
+    }
+    Ok(derives)
+}
+
 fn impl_field_checks(input: &DeriveInput, opts: &DeriveOpts, lifetime: &Lifetime) -> TokenStream2 {
     match &input.data {
         Data::Struct(v) => field_checks(opts, v.fields.iter(), lifetime),
@@ -128,7 +458,7 @@ fn parse_derive_attrs(input: &DeriveInput) -> Result<DeriveOpts, TokenStream2> {
     for attr in zc_attrs {
         let attr_value = attr.tokens.to_string();
 
-        attrs.check = parse_guard_type(&attr, attr_value.as_str())?;
+        attrs.check = parse_guard_type(attr, attr_value.as_str())?;
     }
 
     Ok(attrs)
@@ -151,7 +481,7 @@ fn parse_field_attrs(opts: &DeriveOpts, input: &Field) -> Result<FieldOpts, Toke
     let mut attrs = FieldOpts { guard: opts.check };
 
     for attr in zc_attrs {
-        attrs.guard = parse_guard_type(&attr, attr.tokens.to_string().as_str())?;
+        attrs.guard = parse_guard_type(attr, attr.tokens.to_string().as_str())?;
     }
 
     Ok(attrs)